@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use anyhow::{Result, bail};
 
-use crate::output::{self, OffenderFilter, OutputFormat};
+use crate::output::{self, OffenderCounts, OffenderFilter, OutputFormat, PartialTypes};
 use crate::scanner::{ScannedFile, ScannerConfig, scan};
 
 /// Single source of truth for MVU state.
@@ -28,6 +28,18 @@ pub enum Msg {
     ScanFailed(String),
 }
 
+/// Rendered output plus the facts `lib::run` needs to choose an exit status.
+pub struct Report {
+    pub rendered: String,
+    pub files_with_parse_errors: usize,
+    /// Present when an offender filter was applied.
+    pub offenders: Option<OffenderCounts>,
+    /// Every scanned file, before offender filtering.
+    pub files: Vec<ScannedFile>,
+    /// C# partial types merged across `files`.
+    pub partial_types: PartialTypes,
+}
+
 /// Side-effect requests emitted by `update`.
 enum Command {
     Scan(ScannerConfig),
@@ -56,15 +68,16 @@ impl Model {
     }
 }
 
-/// Executes the MVU loop and returns the rendered report when scanning completes.
+/// Executes the MVU loop and returns the report when scanning completes.
 ///
 /// # Errors
-/// Returns an error if scanning fails or if the MVU state machine does not reach `Completed`.
+/// Returns an error if scanning fails, if the MVU state machine does not reach `Completed`, or
+/// if `format` needs offender limits and `offender_filter` is `None`.
 pub fn run(
     config: ScannerConfig,
     format: OutputFormat,
     offender_filter: Option<&OffenderFilter>,
-) -> Result<String> {
+) -> Result<Report> {
     let mut model = Model::new(config);
     let mut queue = VecDeque::new();
     // Seed the MVU cycle with the initial message.
@@ -79,12 +92,27 @@ pub fn run(
     }
 
     match model.status {
-        Status::Completed => Ok(output::render_report(
-            &model.config,
-            &model.files,
-            format,
-            offender_filter,
-        )),
+        Status::Completed => {
+            let partial_types = PartialTypes::merge(&model.files);
+            let report = output::render_report(
+                &model.config,
+                &model.files,
+                &partial_types,
+                format,
+                offender_filter,
+            )?;
+            Ok(Report {
+                rendered: report.text,
+                files_with_parse_errors: model
+                    .files
+                    .iter()
+                    .filter(|file| file.has_parse_error())
+                    .count(),
+                offenders: report.offenders,
+                files: model.files,
+                partial_types,
+            })
+        }
         Status::Failed => {
             // Propagate failure details collected during the update phase.
             if let Some(message) = model.error {
@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::output::OffenderRule;
+use crate::scanner::path_for_matching;
+
+/// File name searched for in the scan root and each of its ancestors.
+pub const CONFIG_FILE_NAME: &str = "loc-checker.toml";
+
+/// Persistent scan settings; keys mirror the long CLI flag names.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FileSettings {
+    pub exclude: Vec<String>,
+    pub include_path: Vec<String>,
+    pub exclude_path: Vec<String>,
+    pub git_ignore_support: bool,
+    pub test_attributes: Vec<String>,
+    pub offenders_only: bool,
+    pub exclude_tests_from_offenders: bool,
+    pub offending_max_loc_per_file: Option<usize>,
+    pub offending_max_loc_per_fn: Option<usize>,
+    pub offending_max_loc_per_type: Option<usize>,
+    pub offending_max_loc_per_impl_block: Option<usize>,
+    pub offending_max_cyclomatic: Option<usize>,
+    pub offending_max_cognitive: Option<usize>,
+    pub offending_max_nesting: Option<usize>,
+    pub offender_rules: Vec<OffenderRuleSpec>,
+    /// `offender_rules` compiled by [`load`], so their patterns are built only once.
+    #[serde(skip)]
+    pub compiled_offender_rules: Vec<OffenderRule>,
+}
+
+/// Path-scoped offender limits; exactly one of `glob` or `regex` selects the files.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct OffenderRuleSpec {
+    pub name: Option<String>,
+    pub glob: Option<String>,
+    pub regex: Option<String>,
+    pub max_loc_per_file: Option<usize>,
+    pub max_loc_per_fn: Option<usize>,
+}
+
+impl OffenderRuleSpec {
+    /// Compiles the spec into a matcher-backed rule.
+    ///
+    /// # Errors
+    /// Returns an error unless exactly one valid pattern is given and at least one limit is set.
+    pub fn compile(&self) -> Result<OffenderRule> {
+        if self.max_loc_per_file.is_none() && self.max_loc_per_fn.is_none() {
+            bail!("offender rule must set max-loc-per-file or max-loc-per-fn");
+        }
+        if self.max_loc_per_file == Some(0) || self.max_loc_per_fn == Some(0) {
+            bail!("offender rule limits must be greater than zero");
+        }
+        match (&self.glob, &self.regex) {
+            (Some(glob), None) => OffenderRule::glob(
+                self.name.clone(),
+                glob,
+                self.max_loc_per_file,
+                self.max_loc_per_fn,
+            ),
+            (None, Some(regex)) => OffenderRule::regex(
+                self.name.clone(),
+                regex,
+                self.max_loc_per_file,
+                self.max_loc_per_fn,
+            ),
+            _ => bail!("offender rule must set exactly one of glob or regex"),
+        }
+    }
+}
+
+/// Returns the nearest `loc-checker.toml` in `start` or one of its ancestors.
+#[must_use]
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Location of `scan_root` below the directory holding `config_path`, as a prefix for relative
+/// paths: `src/` for a directory, `src/lib.rs` for a file. Empty when the scan root is that
+/// directory or lies outside it, so patterns keep applying relative to `--path`.
+#[must_use]
+pub fn scan_root_prefix(config_path: &Path, scan_root: &Path) -> String {
+    let (Ok(config_path), Ok(scan_root)) = (config_path.canonicalize(), scan_root.canonicalize())
+    else {
+        return String::new();
+    };
+    let Some(offset) = config_path
+        .parent()
+        .and_then(|directory| scan_root.strip_prefix(directory).ok())
+        .filter(|offset| !offset.as_os_str().is_empty())
+    else {
+        return String::new();
+    };
+    let offset = path_for_matching(offset);
+    if scan_root.is_dir() {
+        format!("{offset}/")
+    } else {
+        offset
+    }
+}
+
+/// Reads and parses a settings file and compiles its offender rules.
+///
+/// # Errors
+/// Returns an error when the file cannot be read, is not valid settings TOML, or holds an
+/// invalid threshold or offender rule.
+pub fn load(path: &Path) -> Result<FileSettings> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file {}", path.display()))?;
+    let mut settings: FileSettings = toml::from_str(&contents)
+        .with_context(|| format!("invalid config file {}", path.display()))?;
+    if settings.offending_max_loc_per_file == Some(0)
+        || settings.offending_max_loc_per_fn == Some(0)
+        || settings.offending_max_loc_per_type == Some(0)
+        || settings.offending_max_loc_per_impl_block == Some(0)
+        || settings.offending_max_cyclomatic == Some(0)
+        || settings.offending_max_cognitive == Some(0)
+        || settings.offending_max_nesting == Some(0)
+    {
+        bail!(
+            "offender thresholds in {} must be greater than zero",
+            path.display()
+        );
+    }
+    settings.compiled_offender_rules = settings
+        .offender_rules
+        .iter()
+        .map(|rule| {
+            rule.compile()
+                .with_context(|| format!("invalid offender rule in {}", path.display()))
+        })
+        .collect::<Result<_>>()?;
+    Ok(settings)
+}
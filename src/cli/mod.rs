@@ -1,25 +1,47 @@
+mod config_file;
+
 use std::path::PathBuf;
 
-use clap::Parser;
+use anyhow::{Result, bail};
+use clap::{Args, Parser, Subcommand};
 
 use crate::language::Language;
-use crate::output::{OffenderFilter, OutputFormat};
+use crate::output::{Baseline, OffenderFilter, OffenderRule, OutputFormat};
+use crate::scanner::{CountMode, PatternBases, TopLimit};
 
-#[derive(Parser, Debug)]
-#[command(author, version, about = "Count LOC across source files", long_about = None)]
+#[derive(Parser, Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+#[command(
+    author,
+    version,
+    about = "Count LOC across source files",
+    long_about = None,
+    subcommand_negates_reqs = true
+)]
 pub struct Cli {
-    /// Language to scan (defaults to auto-detection)
-    #[arg(long, value_enum, default_value_t = Language::Auto)]
-    pub lang: Language,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Languages to scan, comma-separated or repeated (defaults to auto-detecting every supported language)
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [Language::Auto])]
+    pub lang: Vec<Language>,
 
     /// Starting path to scan
+    #[arg(long, required = true)]
+    pub path: Option<PathBuf>,
+
+    /// Settings file to load instead of the nearest loc-checker.toml above --path
     #[arg(long)]
-    pub path: PathBuf,
+    pub config: Option<PathBuf>,
 
     /// Enable .gitignore awareness when walking files
-    #[arg(long)]
+    #[arg(long, overrides_with = "no_git_ignore_support")]
     pub git_ignore_support: bool,
 
+    /// Walk files ignored by .gitignore even when the config file enables git-ignore-support
+    #[arg(long = "no-git-ignore-support", overrides_with = "git_ignore_support")]
+    pub no_git_ignore_support: bool,
+
     /// Comma-separated list of relative paths to exclude from scanning
     #[arg(long, value_delimiter = ',')]
     pub exclude: Vec<String>,
@@ -32,24 +54,139 @@ pub struct Cli {
     #[arg(long = "exclude-path", value_delimiter = ',')]
     pub exclude_path: Vec<String>,
 
+    /// Only scan files added or modified since this git ref (compared with the working tree)
+    #[arg(long = "changed-since", value_name = "REF")]
+    pub changed_since: Option<String>,
+
+    /// With --changed-since, only report functions whose lines intersect a changed hunk
+    #[arg(long = "changed-functions-only", requires = "changed_since")]
+    pub changed_functions_only: bool,
+
+    /// Extra attribute names (e.g. `my_crate::test`) that mark Rust functions as tests
+    #[arg(long = "test-attribute", value_delimiter = ',')]
+    pub test_attribute: Vec<String>,
+
+    /// Number of worker threads used to walk and analyze files (defaults to available CPUs)
+    #[arg(long, value_parser = parse_positive_usize)]
+    pub jobs: Option<usize>,
+
+    /// Which lines count toward LOC for thresholds and top-function rankings
+    #[arg(long = "count-mode", global = true, value_enum, default_value_t = CountMode::CodeAndComments)]
+    pub count_mode: CountMode,
+
+    /// Number of longest functions listed per file and across the scan, or `all`
+    #[arg(long, value_name = "N|all")]
+    pub top: Option<TopLimit>,
+
+    /// Exit with an error after reporting when any file fails to parse
+    #[arg(long = "fail-on-parse-error")]
+    pub fail_on_parse_error: bool,
+
     /// Output format for the rendered report
-    #[arg(long = "output-format", value_enum, default_value_t = OutputFormat::Tree)]
+    #[arg(long = "output-format", global = true, value_enum, default_value_t = OutputFormat::Tree)]
     pub output_format: OutputFormat,
 
+    /// Show inline Rust modules nested under each file in the tree output
+    #[arg(long = "show-modules")]
+    pub show_modules: bool,
+
+    /// Expand directories only this many levels deep in the tree output
+    #[arg(long = "max-depth", value_parser = parse_positive_usize)]
+    pub max_depth: Option<usize>,
+
     /// Only print files/functions exceeding LOC limits
+    #[arg(long = "offenders-only", overrides_with = "no_offenders_only")]
+    pub offenders_only: bool,
+
+    /// Print the full report even when the config file enables offenders-only
+    #[arg(long = "no-offenders-only", overrides_with = "offenders_only")]
+    pub no_offenders_only: bool,
+
+    /// Exit with status 3 and print an offender summary to stderr when any limit is exceeded (implies --offenders-only)
+    #[arg(long)]
+    pub check: bool,
+
+    /// Only report offenders missing from this baseline file or larger than recorded (implies --offenders-only)
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Record every current offender to this baseline file (implies --offenders-only)
+    #[arg(long = "write-baseline")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Exit with status 4 when a baselined item got shorter and the baseline was not updated
+    #[arg(long = "fail-on-stale-baseline", requires = "baseline")]
+    pub fail_on_stale_baseline: bool,
+
+    /// Do not report test functions as offenders
     #[arg(
-        long = "offenders-only",
-        requires_all = ["offending_max_loc_per_file", "offending_max_loc_per_fn"]
+        long = "exclude-tests-from-offenders",
+        overrides_with = "include_tests_in_offenders"
     )]
-    pub offenders_only: bool,
+    pub exclude_tests_from_offenders: bool,
+
+    /// Report test functions as offenders even when the config file excludes them
+    #[arg(
+        long = "include-tests-in-offenders",
+        overrides_with = "exclude_tests_from_offenders"
+    )]
+    pub include_tests_in_offenders: bool,
 
     /// Maximum allowed LOC per file when offenders-only mode is enabled
-    #[arg(long = "offending-max-loc-per-file", value_parser = parse_positive_usize, requires = "offenders_only")]
+    #[arg(long = "offending-max-loc-per-file", value_parser = parse_positive_usize)]
     pub offending_max_loc_per_file: Option<usize>,
 
     /// Maximum allowed LOC per function when offenders-only mode is enabled
-    #[arg(long = "offending-max-loc-per-fn", value_parser = parse_positive_usize, requires = "offenders_only")]
+    #[arg(long = "offending-max-loc-per-fn", value_parser = parse_positive_usize)]
     pub offending_max_loc_per_fn: Option<usize>,
+
+    /// Maximum allowed LOC per struct, class, enum or trait definition; C# partial types are
+    /// summed over every file that declares them
+    #[arg(long = "offending-max-loc-per-type", value_parser = parse_positive_usize)]
+    pub offending_max_loc_per_type: Option<usize>,
+
+    /// Maximum allowed LOC per Rust impl block
+    #[arg(long = "offending-max-loc-per-impl-block", value_parser = parse_positive_usize)]
+    pub offending_max_loc_per_impl_block: Option<usize>,
+
+    /// Maximum allowed cyclomatic complexity per function, checked alongside the LOC limit
+    #[arg(long = "offending-max-cyclomatic", value_parser = parse_positive_usize)]
+    pub offending_max_cyclomatic: Option<usize>,
+
+    /// Maximum allowed cognitive complexity per function, checked alongside the LOC limit
+    #[arg(long = "offending-max-cognitive", value_parser = parse_positive_usize)]
+    pub offending_max_cognitive: Option<usize>,
+
+    /// Maximum allowed block nesting depth per function, checked alongside the LOC limit
+    #[arg(long = "offending-max-nesting", value_parser = parse_positive_usize)]
+    pub offending_max_nesting: Option<usize>,
+
+    /// Offender threshold flags given on the command line, before the config file is merged in
+    #[arg(skip)]
+    pub cli_threshold_flags: Vec<&'static str>,
+
+    /// Where the path patterns taken from the config file are anchored
+    #[arg(skip)]
+    pub pattern_bases: PatternBases,
+
+    /// Path-scoped offender limits loaded from the config file
+    #[arg(skip)]
+    pub offender_rules: Vec<OffenderRule>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Compare two JSON reports (or two paths to scan) and show how LOC changed
+    Diff(DiffArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DiffArgs {
+    /// Older side: a report written with --output-format json, or a path to scan
+    pub old: PathBuf,
+
+    /// Newer side: a report written with --output-format json, or a path to scan
+    pub new: PathBuf,
 }
 
 impl Cli {
@@ -58,20 +195,155 @@ impl Cli {
         <Self as Parser>::parse()
     }
 
-    /// # Panics
-    /// Panics if `--offenders-only` is provided without the corresponding LOC thresholds.
-    #[must_use]
-    pub fn offender_filter(&self) -> Option<OffenderFilter> {
-        if self.offenders_only {
-            Some(OffenderFilter::new(
-                self.offending_max_loc_per_file
-                    .expect("clap enforces offenders-only requirements"),
-                self.offending_max_loc_per_fn
-                    .expect("clap enforces offenders-only requirements"),
-            ))
-        } else {
-            None
+    /// Fills settings not given on the command line from `--config`, or from the nearest
+    /// `loc-checker.toml` found by walking up from `--path`.
+    ///
+    /// # Errors
+    /// Returns an error when the settings file cannot be read or parsed, or holds an invalid
+    /// offender rule.
+    pub fn apply_config_file(&mut self) -> Result<()> {
+        self.cli_threshold_flags = self.threshold_flags();
+        let path = self.config.clone().or_else(|| {
+            let root = self.path.as_deref()?;
+            let start = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+            config_file::discover(&start)
+        });
+        let Some(path) = path else {
+            return Ok(());
+        };
+
+        let settings = config_file::load(&path)?;
+        // Paths in the file are relative to its directory, which may sit above `--path`.
+        let prefix = self
+            .path
+            .as_deref()
+            .map(|root| config_file::scan_root_prefix(&path, root))
+            .unwrap_or_default();
+        if self.exclude.is_empty() {
+            self.exclude = settings.exclude;
+            self.pattern_bases.exclude.clone_from(&prefix);
+        }
+        if self.include_path.is_empty() {
+            self.include_path = settings.include_path;
+            self.pattern_bases.include_path.clone_from(&prefix);
+        }
+        if self.exclude_path.is_empty() {
+            self.exclude_path = settings.exclude_path;
+            self.pattern_bases.exclude_path.clone_from(&prefix);
         }
+        if self.test_attribute.is_empty() {
+            self.test_attribute = settings.test_attributes;
+        }
+        // The `--no-*` style flags let the command line switch off booleans the file enables.
+        if !self.no_git_ignore_support {
+            self.git_ignore_support |= settings.git_ignore_support;
+        }
+        if !self.include_tests_in_offenders {
+            self.exclude_tests_from_offenders |= settings.exclude_tests_from_offenders;
+        }
+        if !self.no_offenders_only {
+            self.offenders_only |= settings.offenders_only;
+        }
+        self.offending_max_loc_per_file = self
+            .offending_max_loc_per_file
+            .or(settings.offending_max_loc_per_file);
+        self.offending_max_loc_per_fn = self
+            .offending_max_loc_per_fn
+            .or(settings.offending_max_loc_per_fn);
+        self.offending_max_loc_per_type = self
+            .offending_max_loc_per_type
+            .or(settings.offending_max_loc_per_type);
+        self.offending_max_loc_per_impl_block = self
+            .offending_max_loc_per_impl_block
+            .or(settings.offending_max_loc_per_impl_block);
+        self.offending_max_cyclomatic = self
+            .offending_max_cyclomatic
+            .or(settings.offending_max_cyclomatic);
+        self.offending_max_cognitive = self
+            .offending_max_cognitive
+            .or(settings.offending_max_cognitive);
+        self.offending_max_nesting = self
+            .offending_max_nesting
+            .or(settings.offending_max_nesting);
+        self.offender_rules = settings
+            .compiled_offender_rules
+            .into_iter()
+            .map(|rule| rule.rebased(&prefix))
+            .collect();
+        self.config = Some(path);
+
+        Ok(())
+    }
+
+    /// # Errors
+    /// Returns an error if offender thresholds are given on the command line without an offender
+    /// mode, if an offender mode is enabled without both LOC thresholds, or if the baseline file
+    /// is invalid.
+    pub fn offender_filter(&self) -> Result<Option<OffenderFilter>> {
+        if !(self.offenders_only
+            || self.check
+            || self.baseline.is_some()
+            || self.write_baseline.is_some()
+            || self.output_format.needs_offender_limits())
+        {
+            if !self.cli_threshold_flags.is_empty() {
+                bail!(
+                    "offender thresholds ({}) require an offender mode; add --offenders-only, \
+                     --check, --baseline or --write-baseline",
+                    self.cli_threshold_flags.join(", ")
+                );
+            }
+            return Ok(None);
+        }
+
+        let (Some(max_loc_per_file), Some(max_loc_per_fn)) = (
+            self.offending_max_loc_per_file,
+            self.offending_max_loc_per_fn,
+        ) else {
+            bail!(
+                "offender modes require --offending-max-loc-per-file and \
+                 --offending-max-loc-per-fn (on the command line or in {})",
+                config_file::CONFIG_FILE_NAME
+            );
+        };
+
+        let mut filter = OffenderFilter::new(max_loc_per_file, max_loc_per_fn)
+            .with_rules(self.offender_rules.clone())
+            .with_type_limit(self.offending_max_loc_per_type)
+            .with_impl_block_limit(self.offending_max_loc_per_impl_block)
+            .with_complexity_limits(self.offending_max_cyclomatic, self.offending_max_cognitive)
+            .with_nesting_limit(self.offending_max_nesting)
+            .with_tests_excluded(self.exclude_tests_from_offenders);
+        if let Some(path) = &self.baseline {
+            filter = filter.with_baseline(Baseline::load(path)?);
+        }
+
+        Ok(Some(filter))
+    }
+
+    /// Names of the offender threshold flags that are currently set.
+    fn threshold_flags(&self) -> Vec<&'static str> {
+        [
+            (
+                "--offending-max-loc-per-file",
+                self.offending_max_loc_per_file,
+            ),
+            ("--offending-max-loc-per-fn", self.offending_max_loc_per_fn),
+            (
+                "--offending-max-loc-per-type",
+                self.offending_max_loc_per_type,
+            ),
+            (
+                "--offending-max-loc-per-impl-block",
+                self.offending_max_loc_per_impl_block,
+            ),
+            ("--offending-max-cyclomatic", self.offending_max_cyclomatic),
+            ("--offending-max-cognitive", self.offending_max_cognitive),
+            ("--offending-max-nesting", self.offending_max_nesting),
+        ]
+        .into_iter()
+        .filter_map(|(flag, value)| value.map(|_| flag))
+        .collect()
     }
 }
 
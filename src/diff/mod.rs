@@ -0,0 +1,203 @@
+mod snapshot;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use snapshot::{FileSnapshot, Snapshot};
+
+/// How an entry differs between the old and new side of a diff.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl Change {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Modified => "modified",
+        }
+    }
+
+    fn between(old: Option<usize>, new: Option<usize>) -> Self {
+        match (old, new) {
+            (None, Some(_)) => Change::Added,
+            (Some(_), None) => Change::Removed,
+            _ => Change::Modified,
+        }
+    }
+}
+
+/// Comparison of two snapshots: changed files, directory rollups and changed functions.
+#[derive(Clone, Debug)]
+pub struct ReportDiff {
+    pub old: SideTotals,
+    pub new: SideTotals,
+    /// Files that were added, removed, or whose own or function LOC changed, sorted by path.
+    pub files: Vec<FileDiff>,
+    /// Directories whose file count or total LOC changed, sorted by path.
+    pub directories: Vec<DirectoryDiff>,
+}
+
+/// Label and totals for one side of the diff.
+#[derive(Clone, Debug)]
+pub struct SideTotals {
+    pub label: String,
+    pub files: usize,
+    pub total_loc: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct FileDiff {
+    pub path: String,
+    pub old_loc: Option<usize>,
+    pub new_loc: Option<usize>,
+    /// Functions added, removed or resized within this file, sorted by name.
+    pub functions: Vec<FunctionDiff>,
+}
+
+impl FileDiff {
+    #[must_use]
+    pub fn change(&self) -> Change {
+        Change::between(self.old_loc, self.new_loc)
+    }
+
+    #[must_use]
+    pub fn delta(&self) -> i64 {
+        loc_delta(self.old_loc.unwrap_or(0), self.new_loc.unwrap_or(0))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FunctionDiff {
+    pub name: String,
+    pub old_loc: Option<usize>,
+    pub new_loc: Option<usize>,
+}
+
+impl FunctionDiff {
+    #[must_use]
+    pub fn change(&self) -> Change {
+        Change::between(self.old_loc, self.new_loc)
+    }
+
+    #[must_use]
+    pub fn delta(&self) -> i64 {
+        loc_delta(self.old_loc.unwrap_or(0), self.new_loc.unwrap_or(0))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DirectoryDiff {
+    pub path: String,
+    pub old_files: usize,
+    pub new_files: usize,
+    pub old_loc: usize,
+    pub new_loc: usize,
+}
+
+impl DirectoryDiff {
+    #[must_use]
+    pub fn delta(&self) -> i64 {
+        loc_delta(self.old_loc, self.new_loc)
+    }
+}
+
+impl ReportDiff {
+    #[must_use]
+    pub fn total_delta(&self) -> i64 {
+        loc_delta(self.old.total_loc, self.new.total_loc)
+    }
+}
+
+/// Compares two snapshots keyed by relative path and qualified function name.
+#[must_use]
+pub fn compare(old: &Snapshot, new: &Snapshot) -> ReportDiff {
+    let paths = old
+        .files
+        .keys()
+        .chain(new.files.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut files = Vec::new();
+    let mut directories: BTreeMap<String, DirectoryDiff> = BTreeMap::new();
+    for path in paths {
+        let old_file = old.files.get(path);
+        let new_file = new.files.get(path);
+
+        for directory in ancestors(path) {
+            let rollup =
+                directories
+                    .entry(directory.to_string())
+                    .or_insert_with(|| DirectoryDiff {
+                        path: directory.to_string(),
+                        ..DirectoryDiff::default()
+                    });
+            if let Some(file) = old_file {
+                rollup.old_files += 1;
+                rollup.old_loc += file.total_loc;
+            }
+            if let Some(file) = new_file {
+                rollup.new_files += 1;
+                rollup.new_loc += file.total_loc;
+            }
+        }
+
+        let file = FileDiff {
+            path: path.clone(),
+            old_loc: old_file.map(|file| file.total_loc),
+            new_loc: new_file.map(|file| file.total_loc),
+            functions: compare_functions(old_file, new_file),
+        };
+        if file.old_loc != file.new_loc || !file.functions.is_empty() {
+            files.push(file);
+        }
+    }
+
+    ReportDiff {
+        old: old.totals(),
+        new: new.totals(),
+        files,
+        directories: directories
+            .into_values()
+            .filter(|entry| entry.old_loc != entry.new_loc || entry.old_files != entry.new_files)
+            .collect(),
+    }
+}
+
+fn compare_functions(old: Option<&FileSnapshot>, new: Option<&FileSnapshot>) -> Vec<FunctionDiff> {
+    let empty = BTreeMap::new();
+    let old_functions = old.map_or(&empty, |file| &file.functions);
+    let new_functions = new.map_or(&empty, |file| &file.functions);
+
+    old_functions
+        .keys()
+        .chain(new_functions.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|name| FunctionDiff {
+            name: name.clone(),
+            old_loc: old_functions.get(name).copied(),
+            new_loc: new_functions.get(name).copied(),
+        })
+        .filter(|function| function.old_loc != function.new_loc)
+        .collect()
+}
+
+/// Parent directories of a `/`-separated relative path, outermost first.
+fn ancestors(path: &str) -> impl Iterator<Item = &str> {
+    path.match_indices('/').map(|(index, _)| &path[..index])
+}
+
+/// Signed difference `new - old`, saturating at the `i64` range.
+#[must_use]
+pub fn loc_delta(old: usize, new: usize) -> i64 {
+    if new >= old {
+        i64::try_from(new - old).unwrap_or(i64::MAX)
+    } else {
+        i64::try_from(old - new).map_or(i64::MIN, |delta| -delta)
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::output::{function_locs, qualified_impl_method_name};
+use crate::scanner::{CountMode, ScannedFile, path_for_matching};
+
+use super::SideTotals;
+
+/// Per-file LOC figures that a diff compares, taken from a scan or a saved JSON report.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub label: String,
+    /// `--count-mode` the LOC figures were counted with, by display name.
+    pub count_mode: String,
+    /// Keyed by `/`-separated relative path.
+    pub files: BTreeMap<String, FileSnapshot>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FileSnapshot {
+    pub total_loc: usize,
+    /// Keyed by qualified function name; repeated names keep the largest LOC.
+    pub functions: BTreeMap<String, usize>,
+}
+
+impl FileSnapshot {
+    fn record_function(&mut self, name: String, loc: usize) {
+        let slot = self.functions.entry(name).or_default();
+        *slot = (*slot).max(loc);
+    }
+}
+
+impl Snapshot {
+    /// Builds a snapshot from freshly scanned files.
+    #[must_use]
+    pub fn from_files(label: String, count_mode: CountMode, files: &[ScannedFile]) -> Self {
+        let files = files
+            .iter()
+            .map(|file| {
+                let mut snapshot = FileSnapshot {
+                    total_loc: file.summary.total_loc,
+                    ..FileSnapshot::default()
+                };
+                for (name, loc) in function_locs(&file.summary) {
+                    snapshot.record_function(name, loc);
+                }
+                (path_for_matching(&file.relative_path), snapshot)
+            })
+            .collect();
+
+        Self {
+            label,
+            count_mode: count_mode.display_name().to_string(),
+            files,
+        }
+    }
+
+    /// Reads a report previously written with `--output-format json`.
+    ///
+    /// # Errors
+    /// Returns an error when the file cannot be read, is not a loc-checker JSON report, or was
+    /// written in an offender mode and so lists only the offending files.
+    pub fn load_report(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read report {}", path.display()))?;
+        let report: ReportJson = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not a loc-checker JSON report", path.display()))?;
+        if report.offender_limits.is_some() {
+            bail!(
+                "{} lists only offenders; diff needs a report written without an offender mode",
+                path.display()
+            );
+        }
+
+        let files = report
+            .files
+            .into_iter()
+            .map(|file| {
+                let summary = file.summary;
+                let mut snapshot = FileSnapshot {
+                    total_loc: summary.total_loc,
+                    ..FileSnapshot::default()
+                };
+                for entry in summary
+                    .file_scope_functions
+                    .into_iter()
+                    .chain(summary.test_functions)
+                {
+                    snapshot.record_function(entry.name, entry.loc);
+                }
+                for entry in summary.impl_methods {
+                    snapshot.record_function(
+                        qualified_impl_method_name(
+                            entry.trait_name.as_deref(),
+                            &entry.impl_target,
+                            &entry.method_name,
+                        ),
+                        entry.loc,
+                    );
+                }
+                for entry in summary.trait_methods {
+                    snapshot.record_function(
+                        format!("{}::{}", entry.trait_name, entry.method_name),
+                        entry.loc,
+                    );
+                }
+                (file.path.replace('\\', "/"), snapshot)
+            })
+            .collect();
+
+        Ok(Self {
+            label: path.display().to_string(),
+            count_mode: report.count_mode,
+            files,
+        })
+    }
+
+    /// Checks that `self` and `other` counted lines the same way, so their LOC can be compared.
+    ///
+    /// # Errors
+    /// Returns an error when the two sides used different count modes.
+    pub fn ensure_comparable(&self, other: &Self) -> Result<()> {
+        if self.count_mode != other.count_mode {
+            bail!(
+                "cannot diff {} (count mode {}) against {} (count mode {}); rerun with the same \
+                 --count-mode",
+                self.label,
+                self.count_mode,
+                other.label,
+                other.count_mode
+            );
+        }
+        Ok(())
+    }
+
+    pub(super) fn totals(&self) -> SideTotals {
+        SideTotals {
+            label: self.label.clone(),
+            files: self.files.len(),
+            total_loc: self.files.values().map(|file| file.total_loc).sum(),
+        }
+    }
+}
+
+/// The subset of `output::json::render` output a diff needs.
+#[derive(Deserialize)]
+struct ReportJson {
+    count_mode: String,
+    /// Only present (non-null) in reports written in an offender mode.
+    offender_limits: Option<serde::de::IgnoredAny>,
+    files: Vec<FileJson>,
+}
+
+#[derive(Deserialize)]
+struct FileJson {
+    path: String,
+    summary: SummaryJson,
+}
+
+#[derive(Deserialize)]
+struct SummaryJson {
+    total_loc: usize,
+    #[serde(default)]
+    file_scope_functions: Vec<NamedJson>,
+    #[serde(default)]
+    test_functions: Vec<NamedJson>,
+    #[serde(default)]
+    impl_methods: Vec<ImplMethodJson>,
+    #[serde(default)]
+    trait_methods: Vec<TraitMethodJson>,
+}
+
+#[derive(Deserialize)]
+struct NamedJson {
+    name: String,
+    loc: usize,
+}
+
+#[derive(Deserialize)]
+struct ImplMethodJson {
+    impl_target: String,
+    trait_name: Option<String>,
+    method_name: String,
+    loc: usize,
+}
+
+#[derive(Deserialize)]
+struct TraitMethodJson {
+    trait_name: String,
+    method_name: String,
+    loc: usize,
+}
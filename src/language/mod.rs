@@ -10,6 +10,9 @@ pub enum Language {
 }
 
 impl Language {
+    /// Every concrete language the scanner can analyze, in report order.
+    pub const SUPPORTED: [Language; 2] = [Language::Rust, Language::Csharp];
+
     #[must_use]
     pub fn matches(&self, path: &Path) -> bool {
         match self {
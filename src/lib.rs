@@ -1,20 +1,120 @@
 pub mod app;
 pub mod cli;
+pub mod diff;
 pub mod language;
 pub mod output;
 pub mod scanner;
 
-use anyhow::Result;
+use std::path::Path;
 
-/// Parses CLI arguments, drives the MVU application, and prints the rendered report.
+use anyhow::{Result, bail};
+
+use output::{Baseline, OffenderCounts};
+
+/// Exit status for a successful run.
+pub const EXIT_SUCCESS: i32 = 0;
+/// Exit status for configuration, scan and parse failures.
+pub const EXIT_ERROR: i32 = 1;
+/// Exit status used by `--check` when any file, function or type exceeds its LOC limit.
+pub const EXIT_OFFENDERS_FOUND: i32 = 3;
+/// Exit status for `--fail-on-stale-baseline` when baselined items shrank.
+pub const EXIT_STALE_BASELINE: i32 = 4;
+
+/// Parses CLI arguments, drives the MVU application, prints the rendered report, and returns
+/// the process exit status.
 ///
 /// # Errors
-/// Returns an error when argument conversion or scanning fails.
-pub fn run() -> Result<()> {
-    let args = cli::Cli::parse();
+/// Returns an error when the config file, argument conversion or scanning fails, or when
+/// `--fail-on-parse-error` is set and any file could not be parsed.
+pub fn run() -> Result<i32> {
+    let mut args = cli::Cli::parse();
+    if let Some(cli::Command::Diff(diff_args)) = args.command.take() {
+        return run_diff(&args, &diff_args);
+    }
+
+    args.apply_config_file()?;
     let config = scanner::ScannerConfig::try_from(&args)?;
-    let offender_filter = args.offender_filter();
-    let rendered = app::run(config, args.output_format, offender_filter.as_ref())?;
-    println!("{rendered}");
-    Ok(())
+    let offender_filter = args.offender_filter()?;
+    let report = app::run(config, args.output_format, offender_filter.as_ref())?;
+    println!("{}", report.rendered);
+    if args.fail_on_parse_error && report.files_with_parse_errors > 0 {
+        bail!(
+            "{} file(s) could not be parsed",
+            report.files_with_parse_errors
+        );
+    }
+
+    if let (Some(path), Some(filter)) = (&args.write_baseline, &offender_filter) {
+        Baseline::capture(&report.files, &report.partial_types, filter).write(path)?;
+    }
+
+    if args.check {
+        let counts = report.offenders.unwrap_or_default();
+        eprintln!("{}", check_summary(counts));
+        if !counts.is_empty() {
+            return Ok(EXIT_OFFENDERS_FOUND);
+        }
+    }
+
+    if args.fail_on_stale_baseline
+        && let Some(baseline) = offender_filter
+            .as_ref()
+            .and_then(|filter| filter.baseline.as_ref())
+    {
+        let shrunk = baseline.shrunk_entries(&report.files, &report.partial_types);
+        if !shrunk.is_empty() {
+            eprintln!(
+                "baseline is stale: {} recorded item(s) got shorter; rerun with --write-baseline",
+                shrunk.len()
+            );
+            return Ok(EXIT_STALE_BASELINE);
+        }
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+/// Compares two reports or scanned paths and prints the LOC changes between them.
+fn run_diff(args: &cli::Cli, diff_args: &cli::DiffArgs) -> Result<i32> {
+    let format = output::DiffFormat::try_from(args.output_format)?;
+    let old = diff_side(args, &diff_args.old)?;
+    let new = diff_side(args, &diff_args.new)?;
+    old.ensure_comparable(&new)?;
+    let report = diff::compare(&old, &new);
+    println!("{}", output::render_diff(&report, format));
+    Ok(EXIT_SUCCESS)
+}
+
+/// Loads a saved JSON report, or scans `target` with the remaining command-line settings.
+fn diff_side(args: &cli::Cli, target: &Path) -> Result<diff::Snapshot> {
+    if target.is_file() && target.extension().is_some_and(|ext| ext == "json") {
+        return diff::Snapshot::load_report(target);
+    }
+
+    let mut side = args.clone();
+    side.path = Some(target.to_path_buf());
+    side.apply_config_file()?;
+    let config = scanner::ScannerConfig::try_from(&side)?;
+    let files = scanner::scan(&config)?;
+    Ok(diff::Snapshot::from_files(
+        target.display().to_string(),
+        config.count_mode,
+        &files,
+    ))
+}
+
+fn check_summary(counts: OffenderCounts) -> String {
+    if counts.is_empty() {
+        "check passed: no files or functions exceed configured limits".to_string()
+    } else if counts.types > 0 {
+        format!(
+            "check failed: {} file(s), {} function(s) and {} type(s) exceed configured limits",
+            counts.files, counts.functions, counts.types
+        )
+    } else {
+        format!(
+            "check failed: {} file(s) and {} function(s) exceed configured limits",
+            counts.files, counts.functions
+        )
+    }
 }
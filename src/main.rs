@@ -1,6 +1,9 @@
 fn main() {
-    if let Err(error) = loc_checker::run() {
-        eprintln!("Error: {error}");
-        std::process::exit(1);
+    match loc_checker::run() {
+        Ok(status) => std::process::exit(status),
+        Err(error) => {
+            eprintln!("Error: {error}");
+            std::process::exit(loc_checker::EXIT_ERROR);
+        }
     }
 }
@@ -0,0 +1,45 @@
+use crate::scanner::{ScannedFile, ScannerConfig, path_for_matching};
+
+use super::violations::{Violation, display_path};
+use super::xml::{DECLARATION, escape};
+
+/// Checkstyle format version the report claims to follow.
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+/// Renders every scanned file as a checkstyle `<file>`, with one `<error>` per exceeded limit.
+pub(super) fn render(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    violations: &[Violation],
+) -> String {
+    let mut lines = vec![
+        DECLARATION.to_string(),
+        format!(r#"<checkstyle version="{CHECKSTYLE_VERSION}">"#),
+    ];
+    for file in files {
+        let path = path_for_matching(&file.relative_path);
+        let errors = violations
+            .iter()
+            .filter(|violation| violation.path == path)
+            .collect::<Vec<_>>();
+        let name = escape(&display_path(config, &path));
+        if errors.is_empty() {
+            lines.push(format!(r#"  <file name="{name}"/>"#));
+            continue;
+        }
+        lines.push(format!(r#"  <file name="{name}">"#));
+        for violation in errors {
+            lines.push(format!(
+                r#"    <error line="{}" column="{}" severity="warning" message="{}" source="{}.{}"/>"#,
+                violation.range.start_line,
+                violation.range.start_column.max(1),
+                escape(&violation.message),
+                env!("CARGO_PKG_NAME"),
+                violation.rule_id
+            ));
+        }
+        lines.push("  </file>".to_string());
+    }
+    lines.push("</checkstyle>".to_string());
+    lines.join("\n")
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Error, bail};
+use serde_json::{Value, json};
+
+use crate::diff::{Change, DirectoryDiff, FileDiff, FunctionDiff, ReportDiff};
+
+use super::OutputFormat;
+
+/// The output formats a report comparison can be rendered in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DiffFormat {
+    Tree,
+    Json,
+}
+
+impl TryFrom<OutputFormat> for DiffFormat {
+    type Error = Error;
+
+    fn try_from(format: OutputFormat) -> Result<Self, Self::Error> {
+        match format {
+            OutputFormat::Tree => Ok(Self::Tree),
+            OutputFormat::Json => Ok(Self::Json),
+            OutputFormat::Sarif
+            | OutputFormat::JUnit
+            | OutputFormat::Checkstyle
+            | OutputFormat::Csv
+            | OutputFormat::Tsv
+            | OutputFormat::Markdown => {
+                bail!("the diff subcommand supports only the tree and json output formats")
+            }
+        }
+    }
+}
+
+/// Renders a report comparison in the requested format.
+#[must_use]
+pub fn render_diff(diff: &ReportDiff, format: DiffFormat) -> String {
+    match format {
+        DiffFormat::Tree => render_tree(diff),
+        DiffFormat::Json => render_json(diff),
+    }
+}
+
+fn render_tree(diff: &ReportDiff) -> String {
+    let mut lines = vec![format!(". diff {} -> {}", diff.old.label, diff.new.label)];
+
+    if diff.files.is_empty() {
+        lines.push("└── no LOC changes".to_string());
+    } else {
+        let directories = diff
+            .directories
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry))
+            .collect::<BTreeMap<_, _>>();
+        let mut tree = DiffNode::default();
+        for file in &diff.files {
+            let components = file.path.split('/').collect::<Vec<_>>();
+            tree.insert(&components, file);
+        }
+        tree.render("", "", &directories, &mut lines);
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "totals: {} -> {} files, {} -> {} loc ({:+})",
+        diff.old.files,
+        diff.new.files,
+        diff.old.total_loc,
+        diff.new.total_loc,
+        diff.total_delta()
+    ));
+
+    lines.join("\n")
+}
+
+#[derive(Default)]
+struct DiffNode<'a> {
+    directories: BTreeMap<&'a str, DiffNode<'a>>,
+    files: Vec<(&'a str, &'a FileDiff)>,
+}
+
+impl<'a> DiffNode<'a> {
+    fn insert(&mut self, components: &[&'a str], file: &'a FileDiff) {
+        if let Some((first, rest)) = components.split_first() {
+            if rest.is_empty() {
+                self.files.push((first, file));
+            } else {
+                self.directories
+                    .entry(first)
+                    .or_default()
+                    .insert(rest, file);
+            }
+        }
+    }
+
+    fn render(
+        &self,
+        path: &str,
+        prefix: &str,
+        directories: &BTreeMap<&str, &DirectoryDiff>,
+        lines: &mut Vec<String>,
+    ) {
+        let total = self.directories.len() + self.files.len();
+        let mut index = 0;
+        let next_connector = |index: &mut usize| {
+            *index += 1;
+            let is_last = *index == total;
+            let connector = if is_last { "└──" } else { "├──" };
+            let child_prefix = if is_last {
+                format!("{prefix}    ")
+            } else {
+                format!("{prefix}│   ")
+            };
+            (connector, child_prefix)
+        };
+
+        for (name, child) in &self.directories {
+            let (connector, child_prefix) = next_connector(&mut index);
+            let child_path = if path.is_empty() {
+                (*name).to_string()
+            } else {
+                format!("{path}/{name}")
+            };
+            let figures = directories.get(child_path.as_str()).map_or_else(
+                || "unchanged total".to_string(),
+                |entry| {
+                    format!(
+                        "{} -> {} loc, {:+}",
+                        entry.old_loc,
+                        entry.new_loc,
+                        entry.delta()
+                    )
+                },
+            );
+            lines.push(format!("{prefix}{connector} {name}/ ({figures})"));
+            child.render(&child_path, &child_prefix, directories, lines);
+        }
+
+        for (name, file) in &self.files {
+            let (connector, child_prefix) = next_connector(&mut index);
+            lines.push(format!(
+                "{prefix}{connector} {name} ({})",
+                format_change(file.change(), file.old_loc, file.new_loc, file.delta())
+            ));
+            let count = file.functions.len();
+            for (position, function) in file.functions.iter().enumerate() {
+                let connector = if position + 1 == count {
+                    "└──"
+                } else {
+                    "├──"
+                };
+                lines.push(format!(
+                    "{child_prefix}{connector} {} ({})",
+                    function.name,
+                    format_change(
+                        function.change(),
+                        function.old_loc,
+                        function.new_loc,
+                        function.delta()
+                    )
+                ));
+            }
+        }
+    }
+}
+
+fn format_change(change: Change, old: Option<usize>, new: Option<usize>, delta: i64) -> String {
+    match (change, old, new) {
+        (Change::Added, _, Some(new)) => format!("added, {new} loc"),
+        (Change::Removed, Some(old), _) => format!("removed, {old} loc"),
+        _ => format!(
+            "{} -> {} loc, {delta:+}",
+            old.unwrap_or(0),
+            new.unwrap_or(0)
+        ),
+    }
+}
+
+fn render_json(diff: &ReportDiff) -> String {
+    let files = diff.files.iter().map(file_to_json).collect::<Vec<_>>();
+    let directories = diff
+        .directories
+        .iter()
+        .map(|entry| {
+            json!({
+                "path": entry.path,
+                "old_files": entry.old_files,
+                "new_files": entry.new_files,
+                "old_loc": entry.old_loc,
+                "new_loc": entry.new_loc,
+                "delta": entry.delta(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let functions = diff
+        .files
+        .iter()
+        .flat_map(|file| {
+            file.functions
+                .iter()
+                .map(|function| function_to_json(&file.path, function))
+        })
+        .collect::<Vec<_>>();
+
+    let report = json!({
+        "old": {
+            "label": diff.old.label,
+            "files": diff.old.files,
+            "total_loc": diff.old.total_loc,
+        },
+        "new": {
+            "label": diff.new.label,
+            "files": diff.new.files,
+            "total_loc": diff.new.total_loc,
+        },
+        "total_loc_delta": diff.total_delta(),
+        "files": files,
+        "directories": directories,
+        "functions": functions,
+    });
+
+    serde_json::to_string_pretty(&report).expect("json serialization should succeed")
+}
+
+fn file_to_json(file: &FileDiff) -> Value {
+    json!({
+        "path": file.path,
+        "status": file.change().as_str(),
+        "old_loc": file.old_loc,
+        "new_loc": file.new_loc,
+        "delta": file.delta(),
+    })
+}
+
+fn function_to_json(path: &str, function: &FunctionDiff) -> Value {
+    json!({
+        "path": path,
+        "name": function.name,
+        "status": function.change().as_str(),
+        "old_loc": function.old_loc,
+        "new_loc": function.new_loc,
+        "delta": function.delta(),
+    })
+}
@@ -1,11 +1,25 @@
 use serde_json::{Value, json};
 
+use crate::language::Language;
 use crate::scanner::{
-    ImplBlockLoc, ImplMethodLoc, NamedLoc, RootKind, ScannedFile, ScannerConfig, TraitMethodLoc,
+    ImplBlockKind, ImplBlockLoc, ImplMethodLoc, LineCounts, NamedLoc, RootKind, ScannedFile,
+    ScannerConfig, SourceRange, TopLimit, TraitMethodLoc,
 };
 
+use super::OffenderFilter;
+use super::totals;
+use super::types::MergedType;
+use super::violations::{self, Violation};
+
 #[must_use]
-pub fn render(config: &ScannerConfig, files: &[ScannedFile]) -> String {
+pub fn render(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    scanned_files: &[ScannedFile],
+    merged_types: &[MergedType],
+    files_with_errors: &[&ScannedFile],
+    offender_filter: Option<&OffenderFilter>,
+) -> String {
     let total_loc: usize = files.iter().map(|file| file.summary.total_loc).sum();
     let excludes = config
         .excludes
@@ -25,30 +39,14 @@ pub fn render(config: &ScannerConfig, files: &[ScannedFile]) -> String {
 
     let files_json = files
         .iter()
-        .map(|file| {
-            let summary = &file.summary;
-            json!({
-                "path": file.relative_path.to_string_lossy(),
-                "summary": {
-                    "total_loc": summary.total_loc,
-                    "top_functions": named_locs_to_json(&summary.top_functions),
-                    "file_scope_functions": named_locs_to_json(&summary.file_scope_functions),
-                    "impl_methods": impl_methods_to_json(&summary.impl_methods),
-                    "trait_methods": trait_methods_to_json(&summary.trait_methods),
-                    "test_functions": named_locs_to_json(&summary.test_functions),
-                    "structs": named_locs_to_json(&summary.struct_defs),
-                    "enums": named_locs_to_json(&summary.enum_defs),
-                    "traits": named_locs_to_json(&summary.trait_defs),
-                    "delegates": named_locs_to_json(&summary.delegate_defs),
-                    "events": named_locs_to_json(&summary.event_defs),
-                    "impl_blocks": impl_blocks_to_json(&summary.impl_blocks),
-                    "consts": named_locs_to_json(&summary.consts),
-                    "statics": named_locs_to_json(&summary.statics),
-                }
-            })
-        })
+        .map(|file| file_to_json(file, offender_filter))
         .collect::<Vec<_>>();
 
+    let languages = config
+        .languages
+        .iter()
+        .map(Language::display_name)
+        .collect::<Vec<_>>();
     let report = json!({
         "root": {
             "label": config.root_label(),
@@ -58,31 +56,300 @@ pub fn render(config: &ScannerConfig, files: &[ScannedFile]) -> String {
             },
             "path": config.root().display().to_string(),
         },
-        "language": config.language.display_name(),
+        "language": config.language_label(),
+        "languages": languages,
+        "config_file": config
+            .config_file
+            .as_ref()
+            .map(|path| path.display().to_string()),
+        "count_mode": config.count_mode.display_name(),
+        "top": match config.top {
+            TopLimit::Count(count) => json!(count),
+            TopLimit::All => json!("all"),
+        },
+        "test_attributes": config.test_attributes,
         "git_ignore": config.git_ignore,
         "excludes": excludes,
         "include_path_regexes": include_path_regexes,
         "exclude_path_regexes": exclude_path_regexes,
-        "totals": {
-            "files": files.len(),
-            "total_loc": total_loc,
-        },
+        "changed_since": config.changes.as_ref().map(|changes| json!({
+            "ref": changes.reference,
+            "functions_only": changes.functions_only,
+        })),
+        "offender_limits": offender_filter.map(offender_limits_to_json),
+        "totals": totals_to_json(config, files, total_loc),
         "files": files_json,
+        // Rollups cover every scanned file, also when only offenders are listed.
+        "directories": directories_to_json(scanned_files),
+        "longest_functions": longest_functions_to_json(config, files),
+        "partial_types": merged_types.iter().map(merged_type_to_json).collect::<Vec<_>>(),
+        "diagnostics": diagnostics_to_json(files_with_errors),
     });
 
     serde_json::to_string_pretty(&report).expect("json serialization should succeed")
 }
 
+fn totals_to_json(config: &ScannerConfig, files: &[ScannedFile], total_loc: usize) -> Value {
+    let by_language = totals::by_language(&config.languages, files)
+        .into_iter()
+        .map(|entry| {
+            json!({
+                "language": entry.language.display_name(),
+                "files": entry.files,
+                "total_loc": entry.total_loc,
+            })
+        })
+        .collect::<Vec<_>>();
+    let by_namespace = totals::by_namespace(files)
+        .into_iter()
+        .map(|entry| {
+            json!({
+                "namespace": entry.name,
+                "files": entry.files,
+                "total_loc": entry.total_loc,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "files": files.len(),
+        "total_loc": total_loc,
+        "by_language": by_language,
+        "by_namespace": by_namespace,
+    })
+}
+
+fn longest_functions_to_json(config: &ScannerConfig, files: &[ScannedFile]) -> Vec<Value> {
+    totals::longest_functions(files, config.top)
+        .into_iter()
+        .map(|entry| {
+            json!({
+                "path": entry.path,
+                "name": entry.name,
+                "loc": entry.loc,
+                "location": location_to_json(entry.range),
+            })
+        })
+        .collect()
+}
+
+fn directories_to_json(files: &[ScannedFile]) -> Vec<Value> {
+    totals::by_directory(files)
+        .into_iter()
+        .map(|(path, entry)| {
+            json!({
+                "path": path,
+                "files": entry.files,
+                "total_loc": entry.total_loc,
+                "largest_function": entry.largest_function.map(|(name, loc, file)| json!({
+                    "name": name,
+                    "loc": loc,
+                    "path": file,
+                })),
+            })
+        })
+        .collect()
+}
+
+fn file_to_json(file: &ScannedFile, offender_filter: Option<&OffenderFilter>) -> Value {
+    let summary = &file.summary;
+    let mut entry = json!({
+        "path": file.relative_path.to_string_lossy(),
+        "language": file.language.display_name(),
+        "summary": {
+            "total_loc": summary.total_loc,
+            "lines": lines_to_json(summary.lines),
+            "top_functions": named_locs_to_json(&summary.top_functions),
+            "file_scope_functions": named_locs_to_json(&summary.file_scope_functions),
+            "impl_methods": impl_methods_to_json(&summary.impl_methods),
+            "trait_methods": trait_methods_to_json(&summary.trait_methods),
+            "test_functions": named_locs_to_json(&summary.test_functions),
+            "structs": named_locs_to_json(&summary.struct_defs),
+            "enums": named_locs_to_json(&summary.enum_defs),
+            "traits": named_locs_to_json(&summary.trait_defs),
+            "delegates": named_locs_to_json(&summary.delegate_defs),
+            "events": named_locs_to_json(&summary.event_defs),
+            "impl_blocks": impl_blocks_to_json(&summary.impl_blocks),
+            "consts": named_locs_to_json(&summary.consts),
+            "statics": named_locs_to_json(&summary.statics),
+            "modules": named_locs_to_json(&summary.modules),
+            "namespaces": named_locs_to_json(&summary.namespaces),
+        }
+    });
+
+    if let Some(filter) = offender_filter {
+        let limits = filter.limits_for(&file.relative_path);
+        entry["offense"] = json!({
+            "file": {
+                "rule": limits.file.rule,
+                "max_loc": limits.file.max_loc,
+                "exceeded": summary.total_loc > limits.file.max_loc,
+            },
+            "function": {
+                "rule": limits.function.rule,
+                "max_loc": limits.function.max_loc,
+            },
+        });
+        let violations = violations::collect(std::slice::from_ref(file), &[], filter);
+        attach_offenses(&mut entry["summary"], &violations);
+    }
+
+    entry
+}
+
+/// Adds an `offense` list to each offending function and type entry of a file summary: every
+/// exceeded metric with its actual value, limit and the rule that set the limit.
+fn attach_offenses(summary: &mut Value, violations: &[Violation]) {
+    const LISTS: [(&str, &str); 8] = [
+        ("file_scope_functions", "function-"),
+        ("impl_methods", "function-"),
+        ("trait_methods", "function-"),
+        ("test_functions", "function-"),
+        ("structs", "type-"),
+        ("enums", "type-"),
+        ("traits", "type-"),
+        ("impl_blocks", "impl-block-"),
+    ];
+    for (list, rule_prefix) in LISTS {
+        let Some(entries) = summary[list].as_array_mut() else {
+            continue;
+        };
+        for entry in entries {
+            let offense = violations
+                .iter()
+                .filter(|violation| {
+                    violation.rule_id.starts_with(rule_prefix)
+                        && location_to_json(violation.range) == entry["location"]
+                })
+                .map(|violation| {
+                    json!({
+                        "metric": violation.metric(),
+                        "actual": violation.actual,
+                        "limit": violation.threshold,
+                        "rule": violation.rule,
+                        "rule_id": violation.rule_id,
+                    })
+                })
+                .collect::<Vec<_>>();
+            if !offense.is_empty() {
+                entry["offense"] = Value::Array(offense);
+            }
+        }
+    }
+}
+
+fn offender_limits_to_json(filter: &OffenderFilter) -> Value {
+    let rules = filter
+        .rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "name": rule.name,
+                "kind": rule.pattern_kind(),
+                "pattern": rule.pattern(),
+                "max_loc_per_file": rule.max_loc_per_file,
+                "max_loc_per_fn": rule.max_loc_per_fn,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "max_loc_per_file": filter.max_loc_per_file,
+        "max_loc_per_fn": filter.max_loc_per_fn,
+        "max_loc_per_type": filter.max_loc_per_type,
+        "max_loc_per_impl_block": filter.max_loc_per_impl_block,
+        "max_cyclomatic": filter.max_cyclomatic,
+        "max_cognitive": filter.max_cognitive,
+        "max_nesting": filter.max_nesting,
+        "exclude_tests": filter.exclude_tests,
+        "rules": rules,
+    })
+}
+
+fn merged_type_to_json(merged_type: &MergedType) -> Value {
+    let parts = merged_type
+        .parts
+        .iter()
+        .map(|part| {
+            json!({
+                "path": part.path,
+                "loc": part.loc,
+                "location": location_to_json(part.range),
+            })
+        })
+        .collect::<Vec<_>>();
+    let members = merged_type
+        .members
+        .iter()
+        .map(|member| {
+            json!({
+                "path": member.path,
+                "name": member.name,
+                "loc": member.loc,
+                "location": location_to_json(member.range),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "name": merged_type.name,
+        "total_loc": merged_type.total_loc,
+        "parts": parts,
+        "members": members,
+    })
+}
+
+fn diagnostics_to_json(files_with_errors: &[&ScannedFile]) -> Vec<Value> {
+    files_with_errors
+        .iter()
+        .flat_map(|file| {
+            file.diagnostics.iter().map(|diagnostic| {
+                json!({
+                    "path": file.relative_path.to_string_lossy(),
+                    "kind": diagnostic.kind.as_str(),
+                    "message": diagnostic.message,
+                    "line": diagnostic.line,
+                })
+            })
+        })
+        .collect()
+}
+
+fn lines_to_json(lines: LineCounts) -> Value {
+    json!({
+        "code": lines.code,
+        "comment": lines.comment,
+        "blank": lines.blank,
+    })
+}
+
+fn location_to_json(range: SourceRange) -> Value {
+    json!({
+        "start_line": range.start_line,
+        "start_column": range.start_column,
+        "end_line": range.end_line,
+        "end_column": range.end_column,
+    })
+}
+
 fn named_locs_to_json(entries: &[NamedLoc]) -> Vec<Value> {
     let mut items = entries.to_vec();
     items.sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.name.cmp(&b.name)));
     items
         .into_iter()
         .map(|entry| {
-            json!({
+            let mut item = json!({
                 "name": entry.name,
                 "loc": entry.loc,
-            })
+                "lines": lines_to_json(entry.lines),
+                "location": location_to_json(entry.range),
+            });
+            if let Some(metrics) = entry.metrics {
+                item["cyclomatic"] = json!(metrics.cyclomatic);
+                item["cognitive"] = json!(metrics.cognitive);
+                item["max_nesting"] = json!(metrics.max_nesting);
+            }
+            item
         })
         .collect()
 }
@@ -110,6 +377,11 @@ fn impl_methods_to_json(entries: &[ImplMethodLoc]) -> Vec<Value> {
                 "trait_name": entry.trait_name,
                 "method_name": entry.method_name,
                 "loc": entry.loc,
+                "lines": lines_to_json(entry.lines),
+                "location": location_to_json(entry.range),
+                "cyclomatic": entry.metrics.cyclomatic,
+                "cognitive": entry.metrics.cognitive,
+                "max_nesting": entry.metrics.max_nesting,
             })
         })
         .collect()
@@ -135,6 +407,11 @@ fn trait_methods_to_json(entries: &[TraitMethodLoc]) -> Vec<Value> {
                 "trait_name": entry.trait_name,
                 "method_name": entry.method_name,
                 "loc": entry.loc,
+                "lines": lines_to_json(entry.lines),
+                "location": location_to_json(entry.range),
+                "cyclomatic": entry.metrics.cyclomatic,
+                "cognitive": entry.metrics.cognitive,
+                "max_nesting": entry.metrics.max_nesting,
             })
         })
         .collect()
@@ -159,7 +436,14 @@ fn impl_blocks_to_json(entries: &[ImplBlockLoc]) -> Vec<Value> {
             json!({
                 "impl_target": entry.target,
                 "trait_name": entry.trait_name,
+                "kind": match entry.kind {
+                    ImplBlockKind::Impl => "impl",
+                    ImplBlockKind::TypeBody => "type_body",
+                    ImplBlockKind::InterfaceBody => "interface_body",
+                },
                 "loc": entry.loc,
+                "lines": lines_to_json(entry.lines),
+                "location": location_to_json(entry.range),
             })
         })
         .collect()
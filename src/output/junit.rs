@@ -0,0 +1,145 @@
+use crate::scanner::{ScannedFile, ScannerConfig, path_for_matching};
+
+use super::offenders::function_entries;
+use super::violations::{Violation, display_path};
+use super::xml::{DECLARATION, escape};
+
+/// Test case name for the file-level LOC check.
+const FILE_CASE_NAME: &str = "file LOC";
+
+/// One `JUnit` test case: the file itself, a function, or an offending type.
+struct TestCase<'a> {
+    name: String,
+    /// `(name, start line)` of the item, `None` for the file itself.
+    key: Option<(String, usize)>,
+    failures: Vec<&'a Violation>,
+}
+
+/// Renders every scanned file as a `JUnit` test suite whose cases are the file and its
+/// functions; each exceeded limit fails the case of the item that exceeds it.
+pub(super) fn render(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    violations: &[Violation],
+) -> String {
+    let suites = files
+        .iter()
+        .map(|file| (file, test_cases(file, violations)))
+        .collect::<Vec<_>>();
+    let tests = suites.iter().map(|(_, cases)| cases.len()).sum::<usize>();
+    let failures = suites
+        .iter()
+        .map(|(_, cases)| failed_cases(cases))
+        .sum::<usize>();
+
+    let mut lines = vec![
+        DECLARATION.to_string(),
+        format!(
+            r#"<testsuites name="{}" tests="{tests}" failures="{failures}">"#,
+            env!("CARGO_PKG_NAME")
+        ),
+    ];
+    for (file, cases) in &suites {
+        let path = escape(&display_path(
+            config,
+            &path_for_matching(&file.relative_path),
+        ));
+        lines.push(format!(
+            r#"  <testsuite name="{path}" tests="{}" failures="{}">"#,
+            cases.len(),
+            failed_cases(cases)
+        ));
+        for case in cases {
+            render_case(config, &path, case, &mut lines);
+        }
+        lines.push("  </testsuite>".to_string());
+    }
+    lines.push("</testsuites>".to_string());
+    lines.join("\n")
+}
+
+/// The file case, one case per function in source order, then one per offending type.
+fn test_cases<'a>(file: &ScannedFile, violations: &'a [Violation]) -> Vec<TestCase<'a>> {
+    let mut functions = function_entries(&file.summary);
+    functions.sort_by_key(|entry| (entry.range.start_line, entry.range.start_column));
+    let mut cases = vec![TestCase {
+        name: FILE_CASE_NAME.to_string(),
+        key: None,
+        failures: Vec::new(),
+    }];
+    cases.extend(functions.into_iter().map(|entry| TestCase {
+        key: Some((entry.name.clone(), entry.range.start_line)),
+        name: entry.name,
+        failures: Vec::new(),
+    }));
+
+    let path = path_for_matching(&file.relative_path);
+    for violation in violations.iter().filter(|violation| violation.path == path) {
+        let key = violation
+            .item
+            .as_ref()
+            .map(|item| (item.name.clone(), violation.range.start_line));
+        if let Some(case) = cases.iter_mut().find(|case| case.key == key) {
+            case.failures.push(violation);
+        } else if let Some((name, line)) = key {
+            cases.push(TestCase {
+                name: name.clone(),
+                key: Some((name, line)),
+                failures: vec![violation],
+            });
+        }
+    }
+    cases
+}
+
+fn failed_cases(cases: &[TestCase<'_>]) -> usize {
+    cases
+        .iter()
+        .filter(|case| !case.failures.is_empty())
+        .count()
+}
+
+fn render_case(config: &ScannerConfig, path: &str, case: &TestCase<'_>, lines: &mut Vec<String>) {
+    let name = escape(&case.name);
+    if case.failures.is_empty() {
+        lines.push(format!(
+            r#"    <testcase classname="{path}" name="{name}"/>"#
+        ));
+        return;
+    }
+
+    let message = case
+        .failures
+        .iter()
+        .map(|violation| violation.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let kinds = case
+        .failures
+        .iter()
+        .map(|violation| violation.rule_id)
+        .collect::<Vec<_>>()
+        .join(",");
+    let details = case
+        .failures
+        .iter()
+        .map(|violation| {
+            format!(
+                "{} at {}:{}",
+                violation.message,
+                display_path(config, &violation.path),
+                violation.range.start_line
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    lines.push(format!(
+        r#"    <testcase classname="{path}" name="{name}">"#
+    ));
+    lines.push(format!(
+        r#"      <failure type="{kinds}" message="{}">{}</failure>"#,
+        escape(&message),
+        escape(&details)
+    ));
+    lines.push("    </testcase>".to_string());
+}
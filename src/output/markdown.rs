@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use crate::scanner::{ScannedFile, ScannerConfig, TopLimit, path_for_matching};
+
+use super::totals;
+use super::violations::{Violation, display_path};
+
+/// Renders a Markdown summary for pull-request comments: totals, the largest files and the
+/// longest functions of every scanned file, plus a collapsible offender list when `violations`
+/// is given.
+pub(super) fn render(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    violations: Option<&[Violation]>,
+) -> String {
+    let mut lines = vec![format!("## LOC report for `{}`", code(config.root_label()))];
+    if files.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "No files matched language {}.",
+            config.language_label()
+        ));
+    } else {
+        render_totals(config, files, &mut lines);
+        render_largest_files(config, files, &mut lines);
+        render_longest_functions(config, files, &mut lines);
+    }
+    if let Some(violations) = violations {
+        render_offenders(config, violations, &mut lines);
+    }
+    lines.join("\n")
+}
+
+fn render_totals(config: &ScannerConfig, files: &[ScannedFile], lines: &mut Vec<String>) {
+    let total_loc = files
+        .iter()
+        .map(|file| file.summary.total_loc)
+        .sum::<usize>();
+    lines.push(String::new());
+    lines.push(format!("**{total_loc} LOC** in **{} files**", files.len()));
+    if config.languages.len() > 1 {
+        lines.push(String::new());
+        lines.push("| Language | Files | LOC |".to_string());
+        lines.push("| --- | ---: | ---: |".to_string());
+        for entry in totals::by_language(&config.languages, files) {
+            lines.push(format!(
+                "| {} | {} | {} |",
+                entry.language.display_name(),
+                entry.files,
+                entry.total_loc
+            ));
+        }
+    }
+}
+
+fn render_largest_files(config: &ScannerConfig, files: &[ScannedFile], lines: &mut Vec<String>) {
+    let mut ranked = files.iter().collect::<Vec<_>>();
+    ranked.sort_by(|a, b| {
+        b.summary
+            .total_loc
+            .cmp(&a.summary.total_loc)
+            .then_with(|| a.relative_path.cmp(&b.relative_path))
+    });
+    config.top.truncate(&mut ranked);
+
+    lines.push(String::new());
+    lines.push(heading("Largest files", config.top));
+    lines.push(String::new());
+    lines.push("| File | Language | LOC | Code | Comments | Blank |".to_string());
+    lines.push("| --- | --- | ---: | ---: | ---: | ---: |".to_string());
+    for file in ranked {
+        let counts = &file.summary.lines;
+        lines.push(format!(
+            "| `{}` | {} | {} | {} | {} | {} |",
+            code(&display_path(
+                config,
+                &path_for_matching(&file.relative_path)
+            )),
+            file.language.display_name(),
+            file.summary.total_loc,
+            counts.code,
+            counts.comment,
+            counts.blank
+        ));
+    }
+}
+
+fn render_longest_functions(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    lines: &mut Vec<String>,
+) {
+    let entries = totals::longest_functions(files, config.top);
+    if entries.is_empty() {
+        return;
+    }
+    lines.push(String::new());
+    lines.push(heading("Longest functions", config.top));
+    lines.push(String::new());
+    lines.push("| Function | LOC | Location |".to_string());
+    lines.push("| --- | ---: | --- |".to_string());
+    for entry in entries {
+        let path = path_for_matching(Path::new(&entry.path));
+        lines.push(format!(
+            "| `{}` | {} | `{}:{}` |",
+            code(entry.name),
+            entry.loc,
+            code(&display_path(config, &path)),
+            entry.range.start_line
+        ));
+    }
+}
+
+/// Lists every exceeded limit inside a `<details>` block so long lists stay folded in comments.
+fn render_offenders(config: &ScannerConfig, violations: &[Violation], lines: &mut Vec<String>) {
+    lines.push(String::new());
+    if violations.is_empty() {
+        lines.push("No offenders: every item is within the configured limits.".to_string());
+        return;
+    }
+    let noun = if violations.len() == 1 {
+        "offender"
+    } else {
+        "offenders"
+    };
+    lines.push("<details>".to_string());
+    lines.push(format!(
+        "<summary>{} {noun} over the configured limits</summary>",
+        violations.len()
+    ));
+    lines.push(String::new());
+    lines.push("| Item | Location | Rule | Actual | Limit |".to_string());
+    lines.push("| --- | --- | --- | ---: | ---: |".to_string());
+    for violation in violations {
+        let item = violation.item.as_ref().map_or_else(
+            || "file".to_string(),
+            |item| format!("`{}`", code(&item.name)),
+        );
+        lines.push(format!(
+            "| {item} | `{}:{}` | `{}` | {} | {} |",
+            code(&display_path(config, &violation.path)),
+            violation.range.start_line,
+            violation.rule_id,
+            violation.actual,
+            violation.threshold
+        ));
+    }
+    lines.push(String::new());
+    lines.push("</details>".to_string());
+}
+
+fn heading(title: &str, top: TopLimit) -> String {
+    match top {
+        TopLimit::Count(count) => format!("### {title} (top {count})"),
+        TopLimit::All => format!("### {title}"),
+    }
+}
+
+/// Makes text safe inside a code span in a table cell, where `|` would still end the cell.
+fn code(text: &str) -> String {
+    text.replace('`', "'").replace('|', "\\|")
+}
@@ -1,38 +1,152 @@
+mod checkstyle;
+mod diff;
 mod json;
+mod junit;
+mod markdown;
 mod offenders;
+mod sarif;
+mod table;
+mod totals;
 mod tree;
+mod types;
+mod violations;
+mod xml;
 
 use std::borrow::Cow;
 
+use anyhow::{Context, Result};
 use clap::ValueEnum;
 
 use crate::scanner::{ScannedFile, ScannerConfig};
 
-pub use offenders::OffenderFilter;
+pub use diff::{DiffFormat, render_diff};
+pub use offenders::{
+    AppliedLimit, AppliedLimits, Baseline, BaselineEntry, OffenderCounts, OffenderFilter,
+    OffenderRule,
+};
+pub(crate) use offenders::{function_locs, qualified_impl_method_name};
+pub use types::PartialTypes;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     Tree,
     Json,
+    /// SARIF 2.1.0 log of the offenders; implies offender mode.
+    Sarif,
+    /// XML test report with a test suite per file and a failure per exceeded limit.
+    #[value(name = "junit")]
+    JUnit,
+    /// Checkstyle XML with a `<file>` per file and an `<error>` per exceeded limit.
+    Checkstyle,
+    /// Comma-separated table with one row per function, type, impl block or constant.
+    Csv,
+    /// Tab-separated table with one row per function, type, impl block or constant.
+    Tsv,
+    /// Markdown summary with tables of the largest files and longest functions.
+    Markdown,
 }
 
-#[must_use]
+impl OutputFormat {
+    /// Whether the format is built from offender results, so it needs the offender thresholds.
+    #[must_use]
+    pub fn needs_offender_limits(self) -> bool {
+        matches!(self, Self::Sarif | Self::JUnit | Self::Checkstyle)
+    }
+}
+
+/// A rendered report and, when an offender filter was applied, the offenders it lists.
+pub struct RenderedReport {
+    pub text: String,
+    pub offenders: Option<OffenderCounts>,
+}
+
+/// Renders `files` in `format`, keeping only offenders when `offender_filter` is given.
+///
+/// `partial_types` must be merged from the same unfiltered `files`, since partial types span
+/// files that the offender filter may drop.
+///
+/// # Errors
+/// Returns an error when `format` needs offender limits but no `offender_filter` is given.
 pub fn render_report(
     config: &ScannerConfig,
     files: &[ScannedFile],
+    partial_types: &PartialTypes,
     format: OutputFormat,
     offender_filter: Option<&OffenderFilter>,
-) -> String {
+) -> Result<RenderedReport> {
     let filtered_files: Cow<'_, [ScannedFile]> = if let Some(filter) = offender_filter {
-        Cow::Owned(offenders::filter_files(files, filter))
+        Cow::Owned(offenders::filter_files(files, partial_types, filter))
     } else {
         Cow::Borrowed(files)
     };
+    let merged_types: Cow<'_, [types::MergedType]> = if let Some(filter) = offender_filter {
+        Cow::Owned(offenders::filter_types(partial_types, filter))
+    } else {
+        Cow::Borrowed(partial_types.as_slice())
+    };
+    let offenders = offender_filter
+        .map(|filter| offenders::count_offenders(&filtered_files, &merged_types, filter));
+
+    // Diagnostics are reported for every scanned file, even ones the offender filter drops.
+    let files_with_errors = files
+        .iter()
+        .filter(|file| !file.diagnostics.is_empty())
+        .collect::<Vec<_>>();
 
-    match format {
-        OutputFormat::Tree => {
-            tree::render(config, filtered_files.as_ref(), offender_filter.is_some())
+    let text = match format {
+        OutputFormat::Tree => tree::render(
+            config,
+            filtered_files.as_ref(),
+            files,
+            &merged_types,
+            &files_with_errors,
+            offender_filter,
+        ),
+        OutputFormat::Json => json::render(
+            config,
+            filtered_files.as_ref(),
+            files,
+            &merged_types,
+            &files_with_errors,
+            offender_filter,
+        ),
+        OutputFormat::Sarif => sarif::render(
+            config,
+            filtered_files.as_ref(),
+            &merged_types,
+            required_filter(offender_filter, format)?,
+        ),
+        OutputFormat::JUnit => {
+            let filter = required_filter(offender_filter, format)?;
+            let violations = violations::collect(filtered_files.as_ref(), &merged_types, filter);
+            junit::render(config, files, &violations)
         }
-        OutputFormat::Json => json::render(config, filtered_files.as_ref()),
-    }
+        OutputFormat::Checkstyle => {
+            let filter = required_filter(offender_filter, format)?;
+            let violations = violations::collect(filtered_files.as_ref(), &merged_types, filter);
+            checkstyle::render(config, files, &violations)
+        }
+        OutputFormat::Csv => table::render(filtered_files.as_ref(), table::Delimited::Csv),
+        OutputFormat::Tsv => table::render(filtered_files.as_ref(), table::Delimited::Tsv),
+        OutputFormat::Markdown => {
+            let violations = offender_filter
+                .map(|filter| violations::collect(filtered_files.as_ref(), &merged_types, filter));
+            markdown::render(config, files, violations.as_deref())
+        }
+    };
+    Ok(RenderedReport { text, offenders })
+}
+
+/// The filter that formats built from offender results cannot render without.
+fn required_filter(
+    filter: Option<&OffenderFilter>,
+    format: OutputFormat,
+) -> Result<&OffenderFilter> {
+    filter.with_context(|| {
+        let name = format.to_possible_value().map_or_else(
+            || format!("{format:?}"),
+            |value| value.get_name().to_string(),
+        );
+        format!("{name} output needs offender limits, but no offender filter was given")
+    })
 }
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::{FunctionMetrics, ScannedFile, path_for_matching};
+
+use super::super::types::PartialTypes;
+use super::{
+    OffenderFilter, file_offends, filter_files, filter_types, function_entries, function_locs,
+    type_key, type_locs,
+};
+
+/// Snapshot of known offenders; matching offenders are suppressed until they grow.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Baseline {
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// One recorded offender. `function` is absent for whole-file entries and names types as
+/// `type Name` and impl blocks as `impl Target`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BaselineEntry {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub loc: usize,
+    /// Complexity and nesting of a recorded function, so it offends again when they grow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<FunctionMetrics>,
+}
+
+impl Baseline {
+    /// Reads a baseline previously written with `--write-baseline`.
+    ///
+    /// # Errors
+    /// Returns an error when the file cannot be read or is not a valid baseline.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("invalid baseline file {}", path.display()))
+    }
+
+    /// Writes the baseline as pretty-printed JSON.
+    ///
+    /// # Errors
+    /// Returns an error when the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut contents =
+            serde_json::to_string_pretty(self).context("failed to serialize baseline")?;
+        contents.push('\n');
+        fs::write(path, contents)
+            .with_context(|| format!("failed to write baseline {}", path.display()))
+    }
+
+    /// Records every current offender, ignoring any baseline attached to `filter`.
+    #[must_use]
+    pub fn capture(
+        files: &[ScannedFile],
+        partial_types: &PartialTypes,
+        filter: &OffenderFilter,
+    ) -> Self {
+        let filter = OffenderFilter {
+            baseline: None,
+            ..filter.clone()
+        };
+        let mut entries = Vec::new();
+        for file in filter_files(files, partial_types, &filter) {
+            let path = path_for_matching(&file.relative_path);
+            if file_offends(&file, &filter) {
+                entries.push(BaselineEntry {
+                    path: path.clone(),
+                    function: None,
+                    loc: file.summary.total_loc,
+                    metrics: None,
+                });
+            }
+            for entry in function_entries(&file.summary) {
+                entries.push(BaselineEntry {
+                    path: path.clone(),
+                    function: Some(entry.name),
+                    loc: entry.loc,
+                    metrics: entry.metrics,
+                });
+            }
+            for (function, loc) in type_locs(&file.summary, partial_types) {
+                entries.push(BaselineEntry {
+                    path: path.clone(),
+                    function: Some(function),
+                    loc,
+                    metrics: None,
+                });
+            }
+        }
+        for merged_type in filter_types(partial_types, &filter) {
+            if let Some(first) = merged_type.parts.first() {
+                entries.push(BaselineEntry {
+                    path: first.path.clone(),
+                    function: Some(type_key(&merged_type.name)),
+                    loc: merged_type.total_loc,
+                    metrics: None,
+                });
+            }
+        }
+        entries.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.function.cmp(&b.function))
+                .then(a.loc.cmp(&b.loc))
+        });
+        Self { entries }
+    }
+
+    /// Whether an offender at `loc` is already recorded with at least that many lines.
+    #[must_use]
+    pub fn covers(&self, path: &str, function: Option<&str>, loc: usize) -> bool {
+        self.covers_function(path, function, loc, None)
+    }
+
+    /// Like [`Baseline::covers`], also requiring recorded `metrics` at least as high as the given
+    /// ones; entries without metrics never excuse a complexity or nesting offender.
+    #[must_use]
+    pub fn covers_function(
+        &self,
+        path: &str,
+        function: Option<&str>,
+        loc: usize,
+        metrics: Option<FunctionMetrics>,
+    ) -> bool {
+        self.entries.iter().any(|entry| {
+            entry.path == path
+                && entry.function.as_deref() == function
+                && loc <= entry.loc
+                && metrics.is_none_or(|metrics| {
+                    entry.metrics.is_some_and(|recorded| {
+                        metrics.cyclomatic <= recorded.cyclomatic
+                            && metrics.cognitive <= recorded.cognitive
+                            && metrics.max_nesting <= recorded.max_nesting
+                    })
+                })
+        })
+    }
+
+    /// Entries whose file or function is still present but now has fewer lines than recorded.
+    #[must_use]
+    pub fn shrunk_entries(
+        &self,
+        files: &[ScannedFile],
+        partial_types: &PartialTypes,
+    ) -> Vec<&BaselineEntry> {
+        let mut current: HashMap<(String, Option<String>), usize> = HashMap::new();
+        for file in files {
+            let path = path_for_matching(&file.relative_path);
+            current.insert((path.clone(), None), file.summary.total_loc);
+            for (function, loc) in function_locs(&file.summary)
+                .into_iter()
+                .chain(type_locs(&file.summary, partial_types))
+            {
+                let slot = current.entry((path.clone(), Some(function))).or_default();
+                *slot = (*slot).max(loc);
+            }
+        }
+
+        for merged_type in partial_types.as_slice() {
+            if let Some(first) = merged_type.parts.first() {
+                current.insert(
+                    (first.path.clone(), Some(type_key(&merged_type.name))),
+                    merged_type.total_loc,
+                );
+            }
+        }
+
+        self.entries
+            .iter()
+            .filter(|entry| {
+                current
+                    .get(&(entry.path.clone(), entry.function.clone()))
+                    .is_some_and(|&loc| loc < entry.loc)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,531 @@
+mod baseline;
+mod rules;
+
+use std::path::Path;
+
+use crate::scanner::{
+    FileLocSummary, FunctionMetrics, ImplBlockKind, ImplBlockLoc, ImplMethodLoc, NamedLoc,
+    ScannedFile, SourceRange, TraitMethodLoc, path_for_matching,
+};
+
+use super::types::{MergedType, PartialTypes};
+
+pub use baseline::{Baseline, BaselineEntry};
+pub(crate) use rules::DEFAULT_RULE_NAME;
+pub use rules::{AppliedLimit, AppliedLimits, OffenderRule};
+
+/// Thresholds used to filter out non-offending files and functions.
+#[derive(Clone, Debug)]
+pub struct OffenderFilter {
+    pub max_loc_per_file: usize,
+    pub max_loc_per_fn: usize,
+    /// Limit for struct, class, enum and trait definitions, and for C# partial types summed
+    /// over all of their files; unchecked when unset.
+    pub max_loc_per_type: Option<usize>,
+    /// Limit for a single Rust impl block; unchecked when unset. C# type bodies span the whole
+    /// type, so they are judged by `max_loc_per_type` only.
+    pub max_loc_per_impl_block: Option<usize>,
+    /// Functions above this cyclomatic complexity offend regardless of their LOC.
+    pub max_cyclomatic: Option<usize>,
+    /// Functions above this cognitive complexity offend regardless of their LOC.
+    pub max_cognitive: Option<usize>,
+    /// Functions nested deeper than this offend regardless of their LOC.
+    pub max_nesting: Option<usize>,
+    pub rules: Vec<OffenderRule>,
+    pub baseline: Option<Baseline>,
+    /// Never report test functions, whatever their size.
+    pub exclude_tests: bool,
+}
+
+impl OffenderFilter {
+    #[must_use]
+    pub const fn new(max_loc_per_file: usize, max_loc_per_fn: usize) -> Self {
+        Self {
+            max_loc_per_file,
+            max_loc_per_fn,
+            max_loc_per_type: None,
+            max_loc_per_impl_block: None,
+            max_cyclomatic: None,
+            max_cognitive: None,
+            max_nesting: None,
+            rules: Vec::new(),
+            baseline: None,
+            exclude_tests: false,
+        }
+    }
+
+    /// Adds path-scoped rules that override the global thresholds for matching files.
+    #[must_use]
+    pub fn with_rules(mut self, rules: Vec<OffenderRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Checks type definitions and merged partial types against `max_loc` when it is set.
+    #[must_use]
+    pub fn with_type_limit(mut self, max_loc: Option<usize>) -> Self {
+        self.max_loc_per_type = max_loc;
+        self
+    }
+
+    /// Checks each impl block against `max_loc` when it is set.
+    #[must_use]
+    pub fn with_impl_block_limit(mut self, max_loc: Option<usize>) -> Self {
+        self.max_loc_per_impl_block = max_loc;
+        self
+    }
+
+    /// Flags functions whose cyclomatic or cognitive complexity exceeds the given limits.
+    #[must_use]
+    pub fn with_complexity_limits(
+        mut self,
+        max_cyclomatic: Option<usize>,
+        max_cognitive: Option<usize>,
+    ) -> Self {
+        self.max_cyclomatic = max_cyclomatic;
+        self.max_cognitive = max_cognitive;
+        self
+    }
+
+    /// Flags functions whose block nesting is deeper than `max_nesting`.
+    #[must_use]
+    pub fn with_nesting_limit(mut self, max_nesting: Option<usize>) -> Self {
+        self.max_nesting = max_nesting;
+        self
+    }
+
+    /// Leaves test functions out of the offender report when `exclude` is set.
+    #[must_use]
+    pub fn with_tests_excluded(mut self, exclude: bool) -> Self {
+        self.exclude_tests = exclude;
+        self
+    }
+
+    /// Suppresses offenders already recorded in `baseline` unless their LOC grew.
+    #[must_use]
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Whether a function's complexity or nesting exceeds a configured limit.
+    #[must_use]
+    pub fn exceeds_complexity(&self, metrics: Option<FunctionMetrics>) -> bool {
+        metrics.is_some_and(|metrics| !self.exceeded_metrics(metrics).is_empty())
+    }
+
+    /// `(metric, actual, limit)` for each complexity or nesting limit `metrics` exceeds.
+    #[must_use]
+    pub fn exceeded_metrics(&self, metrics: FunctionMetrics) -> Vec<(&'static str, usize, usize)> {
+        [
+            ("cyclomatic", metrics.cyclomatic, self.max_cyclomatic),
+            ("cognitive", metrics.cognitive, self.max_cognitive),
+            ("nesting", metrics.max_nesting, self.max_nesting),
+        ]
+        .into_iter()
+        .filter_map(|(metric, actual, max)| {
+            max.filter(|&max| actual > max)
+                .map(|max| (metric, actual, max))
+        })
+        .collect()
+    }
+
+    /// Resolves the file and function limits that apply to a relative path.
+    #[must_use]
+    pub fn limits_for(&self, relative_path: &Path) -> AppliedLimits {
+        rules::resolve(
+            &self.rules,
+            &path_for_matching(relative_path),
+            self.max_loc_per_file,
+            self.max_loc_per_fn,
+        )
+    }
+}
+
+/// Number of files, functions and types over their limits, as summarized by `--check`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OffenderCounts {
+    pub files: usize,
+    pub functions: usize,
+    pub types: usize,
+}
+
+impl OffenderCounts {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files == 0 && self.functions == 0 && self.types == 0
+    }
+}
+
+/// Counts the offenders left after filtering: `files` and `types` are the output of
+/// [`filter_files`] and [`filter_types`].
+pub(crate) fn count_offenders(
+    files: &[ScannedFile],
+    types: &[MergedType],
+    filter: &OffenderFilter,
+) -> OffenderCounts {
+    let counts = OffenderCounts {
+        types: types.len(),
+        ..OffenderCounts::default()
+    };
+    files.iter().fold(counts, |mut counts, file| {
+        if file_offends(file, filter) {
+            counts.files += 1;
+        }
+        // `top_functions` repeats entries from the per-kind lists, so it is not counted.
+        counts.functions += file.summary.file_scope_functions.len()
+            + file.summary.impl_methods.len()
+            + file.summary.trait_methods.len()
+            + file.summary.test_functions.len();
+        counts.types += file.summary.struct_defs.len()
+            + file.summary.enum_defs.len()
+            + file.summary.trait_defs.len()
+            + file.summary.impl_blocks.len();
+        counts
+    })
+}
+
+/// Returns only the files (and function entries) that exceed the configured LOC limits.
+///
+/// When a baseline is attached, offenders recorded in it are dropped unless they grew.
+#[must_use]
+pub(crate) fn filter_files(
+    files: &[ScannedFile],
+    partial_types: &PartialTypes,
+    filter: &OffenderFilter,
+) -> Vec<ScannedFile> {
+    files
+        .iter()
+        .filter_map(|file| {
+            let limits = filter.limits_for(&file.relative_path);
+            let path = path_for_matching(&file.relative_path);
+            let is_offender = |name: &str, loc: usize, metrics: Option<FunctionMetrics>| {
+                let too_complex = filter.exceeds_complexity(metrics);
+                (loc > limits.function.max_loc || too_complex)
+                    && !filter.baseline.as_ref().is_some_and(|baseline| {
+                        // A LOC-only entry must not excuse growing complexity.
+                        let metrics = metrics.filter(|_| too_complex);
+                        baseline.covers_function(&path, Some(name), loc, metrics)
+                    })
+            };
+            let is_baselined = |key: &str, loc: usize| {
+                filter
+                    .baseline
+                    .as_ref()
+                    .is_some_and(|baseline| baseline.covers(&path, Some(key), loc))
+            };
+            let filtered_summary = filter_summary(
+                &file.summary,
+                partial_types,
+                filter,
+                &is_offender,
+                &is_baselined,
+            );
+            let file_exceeds = file_offends(file, filter);
+            let has_function_offenders = summary_has_function_offenders(&filtered_summary);
+            let has_type_offenders = summary_has_type_offenders(&filtered_summary);
+
+            if file_exceeds || has_function_offenders || has_type_offenders {
+                Some(ScannedFile {
+                    relative_path: file.relative_path.clone(),
+                    language: file.language,
+                    summary: filtered_summary,
+                    diagnostics: file.diagnostics.clone(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Keeps the merged types whose combined LOC exceeds the per-type limit; none without one.
+///
+/// A merged type is baselined under the path of its first declaration.
+pub(crate) fn filter_types(types: &PartialTypes, filter: &OffenderFilter) -> Vec<MergedType> {
+    let Some(max_loc) = filter.max_loc_per_type else {
+        return Vec::new();
+    };
+    types
+        .as_slice()
+        .iter()
+        .filter(|merged_type| {
+            merged_type.total_loc > max_loc
+                && !filter.baseline.as_ref().is_some_and(|baseline| {
+                    merged_type.parts.first().is_some_and(|part| {
+                        baseline.covers(
+                            &part.path,
+                            Some(&type_key(&merged_type.name)),
+                            merged_type.total_loc,
+                        )
+                    })
+                })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether the file as a whole exceeds its limit and is not excused by the baseline.
+pub(crate) fn file_offends(file: &ScannedFile, filter: &OffenderFilter) -> bool {
+    let limits = filter.limits_for(&file.relative_path);
+    file.summary.total_loc > limits.file.max_loc
+        && !filter.baseline.as_ref().is_some_and(|baseline| {
+            baseline.covers(
+                &path_for_matching(&file.relative_path),
+                None,
+                file.summary.total_loc,
+            )
+        })
+}
+
+type OffenderPredicate<'a> = dyn Fn(&str, usize, Option<FunctionMetrics>) -> bool + 'a;
+
+/// Whether the baseline records the item with this key at no fewer lines.
+type BaselinePredicate<'a> = dyn Fn(&str, usize) -> bool + 'a;
+
+/// Keeps only the offending functions, types and impl blocks of `summary`.
+fn filter_summary(
+    summary: &FileLocSummary,
+    partial_types: &PartialTypes,
+    filter: &OffenderFilter,
+    is_offender: &OffenderPredicate<'_>,
+    is_baselined: &BaselinePredicate<'_>,
+) -> FileLocSummary {
+    // Partial declarations are judged by their merged size instead (see `filter_types`).
+    let whole_types = |entries: &[NamedLoc]| {
+        entries
+            .iter()
+            .filter(|entry| !partial_types.contains(&entry.name))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let mut filtered = FileLocSummary {
+        total_loc: summary.total_loc,
+        lines: summary.lines,
+        top_functions: Vec::new(),
+        file_scope_functions: filter_named(&summary.file_scope_functions, is_offender),
+        impl_methods: filter_impl_methods(&summary.impl_methods, is_offender),
+        trait_methods: filter_trait_methods(&summary.trait_methods, is_offender),
+        test_functions: if filter.exclude_tests {
+            Vec::new()
+        } else {
+            filter_named(&summary.test_functions, is_offender)
+        },
+        struct_defs: oversized(
+            &whole_types(&summary.struct_defs),
+            filter.max_loc_per_type,
+            is_baselined,
+        ),
+        enum_defs: oversized(&summary.enum_defs, filter.max_loc_per_type, is_baselined),
+        trait_defs: oversized(
+            &whole_types(&summary.trait_defs),
+            filter.max_loc_per_type,
+            is_baselined,
+        ),
+        delegate_defs: summary.delegate_defs.clone(),
+        event_defs: summary.event_defs.clone(),
+        impl_blocks: oversized_impl_blocks(
+            &summary.impl_blocks,
+            filter.max_loc_per_impl_block,
+            is_baselined,
+        ),
+        consts: summary.consts.clone(),
+        statics: summary.statics.clone(),
+        modules: summary.modules.clone(),
+        namespaces: summary.namespaces.clone(),
+        partial_types: summary.partial_types.clone(),
+    };
+    // `top_functions` uses display names rather than the qualified names offenders are matched
+    // by, so it keeps the entries whose functions survived in the per-kind lists.
+    let kept = function_entries(&filtered)
+        .into_iter()
+        .map(|entry| entry.range)
+        .collect::<Vec<_>>();
+    filtered.top_functions = summary
+        .top_functions
+        .iter()
+        .filter(|entry| kept.contains(&entry.range))
+        .cloned()
+        .collect();
+    filtered
+}
+
+fn filter_named(entries: &[NamedLoc], is_offender: &OffenderPredicate<'_>) -> Vec<NamedLoc> {
+    entries
+        .iter()
+        .filter(|entry| is_offender(&entry.name, entry.loc, entry.metrics))
+        .cloned()
+        .collect()
+}
+
+/// Entries over `max_loc` that the baseline does not excuse; nothing offends when the limit is
+/// unset.
+fn oversized(
+    entries: &[NamedLoc],
+    max_loc: Option<usize>,
+    is_baselined: &BaselinePredicate<'_>,
+) -> Vec<NamedLoc> {
+    let Some(max_loc) = max_loc else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|entry| entry.loc > max_loc && !is_baselined(&type_key(&entry.name), entry.loc))
+        .cloned()
+        .collect()
+}
+
+/// Rust impl blocks over `max_loc`. C# type bodies repeat their type definition, which the type
+/// limit already covers, so they never offend as impl blocks.
+fn oversized_impl_blocks(
+    entries: &[ImplBlockLoc],
+    max_loc: Option<usize>,
+    is_baselined: &BaselinePredicate<'_>,
+) -> Vec<ImplBlockLoc> {
+    let Some(max_loc) = max_loc else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|entry| {
+            entry.kind == ImplBlockKind::Impl
+                && entry.loc > max_loc
+                && !is_baselined(&entry.display_name(), entry.loc)
+        })
+        .cloned()
+        .collect()
+}
+
+fn filter_impl_methods(
+    entries: &[ImplMethodLoc],
+    is_offender: &OffenderPredicate<'_>,
+) -> Vec<ImplMethodLoc> {
+    entries
+        .iter()
+        .filter(|entry| is_offender(&impl_method_name(entry), entry.loc, Some(entry.metrics)))
+        .cloned()
+        .collect()
+}
+
+fn filter_trait_methods(
+    entries: &[TraitMethodLoc],
+    is_offender: &OffenderPredicate<'_>,
+) -> Vec<TraitMethodLoc> {
+    entries
+        .iter()
+        .filter(|entry| is_offender(&trait_method_name(entry), entry.loc, Some(entry.metrics)))
+        .cloned()
+        .collect()
+}
+
+/// Qualified name used to identify an impl method across runs.
+pub(crate) fn impl_method_name(entry: &ImplMethodLoc) -> String {
+    qualified_impl_method_name(
+        entry.trait_name.as_deref(),
+        &entry.impl_target,
+        &entry.method_name,
+    )
+}
+
+pub(crate) fn qualified_impl_method_name(
+    trait_name: Option<&str>,
+    impl_target: &str,
+    method_name: &str,
+) -> String {
+    match trait_name {
+        Some(trait_name) => format!("{trait_name} for {impl_target}::{method_name}"),
+        None => format!("{impl_target}::{method_name}"),
+    }
+}
+
+/// Qualified name used to identify a trait method across runs.
+pub(crate) fn trait_method_name(entry: &TraitMethodLoc) -> String {
+    format!("{}::{}", entry.trait_name, entry.method_name)
+}
+
+/// Key a type definition is recorded under in a baseline, e.g. `type Shop.Customer`.
+pub(crate) fn type_key(name: &str) -> String {
+    format!("type {name}")
+}
+
+/// Baseline keys and LOC of the type definitions and Rust impl blocks in `summary`.
+///
+/// Partial declarations are left out, since merged partial types are recorded as a whole.
+pub(crate) fn type_locs(
+    summary: &FileLocSummary,
+    partial_types: &PartialTypes,
+) -> Vec<(String, usize)> {
+    summary
+        .struct_defs
+        .iter()
+        .chain(&summary.enum_defs)
+        .chain(&summary.trait_defs)
+        .filter(|entry| !partial_types.contains(&entry.name))
+        .map(|entry| (type_key(&entry.name), entry.loc))
+        .chain(
+            summary
+                .impl_blocks
+                .iter()
+                .filter(|block| block.kind == ImplBlockKind::Impl)
+                .map(|block| (block.display_name(), block.loc)),
+        )
+        .collect()
+}
+
+/// Qualified function names and LOC for every function kind except the `top_functions` digest.
+pub(crate) fn function_locs(summary: &FileLocSummary) -> Vec<(String, usize)> {
+    function_entries(summary)
+        .into_iter()
+        .map(|entry| (entry.name, entry.loc))
+        .collect()
+}
+
+/// A function of any kind, under its qualified name.
+pub(crate) struct FunctionEntry {
+    pub name: String,
+    pub loc: usize,
+    pub range: SourceRange,
+    pub metrics: Option<FunctionMetrics>,
+}
+
+/// Like [`function_locs`], with the source range and metrics of each function.
+pub(crate) fn function_entries(summary: &FileLocSummary) -> Vec<FunctionEntry> {
+    summary
+        .file_scope_functions
+        .iter()
+        .chain(&summary.test_functions)
+        .map(|entry| FunctionEntry {
+            name: entry.name.clone(),
+            loc: entry.loc,
+            range: entry.range,
+            metrics: entry.metrics,
+        })
+        .chain(summary.impl_methods.iter().map(|entry| FunctionEntry {
+            name: impl_method_name(entry),
+            loc: entry.loc,
+            range: entry.range,
+            metrics: Some(entry.metrics),
+        }))
+        .chain(summary.trait_methods.iter().map(|entry| FunctionEntry {
+            name: trait_method_name(entry),
+            loc: entry.loc,
+            range: entry.range,
+            metrics: Some(entry.metrics),
+        }))
+        .collect()
+}
+
+fn summary_has_function_offenders(summary: &FileLocSummary) -> bool {
+    !(summary.file_scope_functions.is_empty()
+        && summary.impl_methods.is_empty()
+        && summary.trait_methods.is_empty()
+        && summary.test_functions.is_empty())
+}
+
+fn summary_has_type_offenders(summary: &FileLocSummary) -> bool {
+    !(summary.struct_defs.is_empty()
+        && summary.enum_defs.is_empty()
+        && summary.trait_defs.is_empty()
+        && summary.impl_blocks.is_empty())
+}
@@ -0,0 +1,177 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+
+/// Name reported for limits that come from the global thresholds rather than a path rule.
+pub(crate) const DEFAULT_RULE_NAME: &str = "default";
+
+/// Path-scoped override of the global offender thresholds.
+#[derive(Clone, Debug)]
+pub struct OffenderRule {
+    pub name: String,
+    pattern: RulePattern,
+    /// Put in front of scanned paths before matching; see [`OffenderRule::rebased`].
+    base: String,
+    pub max_loc_per_file: Option<usize>,
+    pub max_loc_per_fn: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum RulePattern {
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+/// A single limit together with the rule that supplied it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppliedLimit {
+    pub rule: String,
+    pub max_loc: usize,
+}
+
+/// Effective file and function limits for one path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppliedLimits {
+    pub file: AppliedLimit,
+    pub function: AppliedLimit,
+}
+
+impl OffenderRule {
+    /// Builds a rule matching relative paths against a glob (`*` stays within one directory).
+    ///
+    /// # Errors
+    /// Returns an error when the glob is invalid.
+    pub fn glob(
+        name: Option<String>,
+        pattern: &str,
+        max_loc_per_file: Option<usize>,
+        max_loc_per_fn: Option<usize>,
+    ) -> Result<Self> {
+        let matcher = globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map(|glob: Glob| glob.compile_matcher())
+            .with_context(|| format!("invalid offender rule glob: {pattern}"))?;
+        Ok(Self {
+            name: name.unwrap_or_else(|| pattern.to_string()),
+            pattern: RulePattern::Glob(matcher),
+            base: String::new(),
+            max_loc_per_file,
+            max_loc_per_fn,
+        })
+    }
+
+    /// Builds a rule matching relative paths against a regex.
+    ///
+    /// # Errors
+    /// Returns an error when the regex is invalid.
+    pub fn regex(
+        name: Option<String>,
+        pattern: &str,
+        max_loc_per_file: Option<usize>,
+        max_loc_per_fn: Option<usize>,
+    ) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .with_context(|| format!("invalid offender rule regex: {pattern}"))?;
+        Ok(Self {
+            name: name.unwrap_or_else(|| pattern.to_string()),
+            pattern: RulePattern::Regex(regex),
+            base: String::new(),
+            max_loc_per_file,
+            max_loc_per_fn,
+        })
+    }
+
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        match &self.pattern {
+            RulePattern::Glob(matcher) => matcher.glob().glob(),
+            RulePattern::Regex(regex) => regex.as_str(),
+        }
+    }
+
+    #[must_use]
+    pub fn pattern_kind(&self) -> &'static str {
+        match &self.pattern {
+            RulePattern::Glob(_) => "glob",
+            RulePattern::Regex(_) => "regex",
+        }
+    }
+
+    /// Matches the pattern against scanned paths prefixed with `base`, the scan root's location
+    /// below the directory the pattern was written for.
+    #[must_use]
+    pub fn rebased(self, base: &str) -> Self {
+        Self {
+            base: base.to_string(),
+            ..self
+        }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        let path = format!("{}{path}", self.base);
+        match &self.pattern {
+            RulePattern::Glob(matcher) => matcher.is_match(&path),
+            RulePattern::Regex(regex) => regex.is_match(&path),
+        }
+    }
+
+    /// Number of literal characters in the pattern; longer literals are more specific.
+    fn specificity(&self) -> usize {
+        match &self.pattern {
+            RulePattern::Glob(_) => literal_len(self.pattern(), "*?[]{}!"),
+            RulePattern::Regex(_) => literal_len(self.pattern(), ".^$|?*+()[]{}"),
+        }
+    }
+}
+
+/// Resolves each limit from the most specific matching rule that sets it, falling back to the
+/// global default. Ties go to the rule declared last.
+pub(super) fn resolve(
+    rules: &[OffenderRule],
+    path: &str,
+    default_file: usize,
+    default_fn: usize,
+) -> AppliedLimits {
+    let mut matching = rules
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| rule.matches(path))
+        .collect::<Vec<_>>();
+    matching.sort_by_key(|(index, rule)| std::cmp::Reverse((rule.specificity(), *index)));
+
+    let pick = |limit: fn(&OffenderRule) -> Option<usize>, default: usize| {
+        matching
+            .iter()
+            .find_map(|(_, rule)| {
+                limit(rule).map(|max_loc| AppliedLimit {
+                    rule: rule.name.clone(),
+                    max_loc,
+                })
+            })
+            .unwrap_or_else(|| AppliedLimit {
+                rule: DEFAULT_RULE_NAME.to_string(),
+                max_loc: default,
+            })
+    };
+
+    AppliedLimits {
+        file: pick(|rule| rule.max_loc_per_file, default_file),
+        function: pick(|rule| rule.max_loc_per_fn, default_fn),
+    }
+}
+
+fn literal_len(pattern: &str, metacharacters: &str) -> usize {
+    let mut count = 0;
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if chars.next().is_some() {
+                count += 1;
+            }
+        } else if !metacharacters.contains(ch) {
+            count += 1;
+        }
+    }
+    count
+}
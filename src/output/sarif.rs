@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::scanner::{ScannedFile, ScannerConfig, SourceRange};
+
+use super::OffenderFilter;
+use super::types::MergedType;
+use super::violations::{self, RULES, Violation, display_path};
+
+const SCHEMA_URI: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Renders offenders as a SARIF 2.1.0 log with one result per exceeded limit.
+///
+/// `files` and `merged_types` must already be filtered down to offenders.
+#[must_use]
+pub fn render(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    merged_types: &[MergedType],
+    filter: &OffenderFilter,
+) -> String {
+    let results = violations::collect(files, merged_types, filter)
+        .iter()
+        .map(|violation| result(config, violation))
+        .collect::<Vec<_>>();
+
+    let rules = RULES
+        .iter()
+        .map(|(id, description)| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": description },
+                "defaultConfiguration": { "level": "warning" },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let log = json!({
+        "$schema": SCHEMA_URI,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).expect("sarif serialization should succeed")
+}
+
+fn result(config: &ScannerConfig, violation: &Violation) -> Value {
+    let rule_index = RULES
+        .iter()
+        .position(|(id, _)| *id == violation.rule_id)
+        .expect("every reported rule is listed in RULES");
+    let item = violation
+        .item
+        .as_ref()
+        .map(|item| (item.name.as_str(), item.kind));
+
+    let mut value = json!({
+        "ruleId": violation.rule_id,
+        "ruleIndex": rule_index,
+        "level": "warning",
+        "message": { "text": violation.message },
+        "locations": [location(config, &violation.path, violation.range, item)],
+        "properties": {
+            "actual": violation.actual,
+            "threshold": violation.threshold,
+        },
+    });
+    if !violation.related.is_empty() {
+        value["relatedLocations"] = violation
+            .related
+            .iter()
+            .enumerate()
+            .map(|(index, (path, range))| {
+                let mut related = location(config, path, *range, item);
+                related["id"] = json!(index + 1);
+                related
+            })
+            .collect();
+    }
+    value
+}
+
+/// A physical location, plus a logical one naming the item when given `(name, kind)`.
+fn location(
+    config: &ScannerConfig,
+    path: &str,
+    range: SourceRange,
+    item: Option<(&str, &str)>,
+) -> Value {
+    let mut region = json!({
+        "startLine": range.start_line,
+        "startColumn": range.start_column.max(1),
+        "endLine": range.end_line,
+    });
+    // SARIF end columns point just past the region, one beyond the last character.
+    if range.end_column > 0 {
+        region["endColumn"] = json!(range.end_column + 1);
+    }
+    let mut value = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": artifact_uri(config, path) },
+            "region": region,
+        },
+    });
+    if let Some((name, kind)) = item {
+        value["logicalLocations"] = json!([{
+            "fullyQualifiedName": name,
+            "kind": kind,
+        }]);
+    }
+    value
+}
+
+/// Relative paths become URIs resolved against the working directory; absolute ones become
+/// `file://` URIs. Characters outside the URI unreserved set are percent-encoded.
+fn artifact_uri(config: &ScannerConfig, path: &str) -> String {
+    let path = display_path(config, path);
+    if Path::new(&path).is_absolute() {
+        // Drive letters such as `C:` keep their colon.
+        format!(
+            "file:///{}",
+            percent_encode(path.trim_start_matches('/'), b"/:")
+        )
+    } else {
+        percent_encode(&path, b"/")
+    }
+}
+
+/// Percent-encodes every byte except ASCII alphanumerics, `-._~` and the bytes in `keep`.
+fn percent_encode(text: &str, keep: &[u8]) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) || keep.contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            const HEX: &[u8; 16] = b"0123456789ABCDEF";
+            encoded.push('%');
+            encoded.push(char::from(HEX[usize::from(byte >> 4)]));
+            encoded.push(char::from(HEX[usize::from(byte & 0x0f)]));
+        }
+    }
+    encoded
+}
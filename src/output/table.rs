@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+
+use crate::language::Language;
+use crate::scanner::{FileLocSummary, NamedLoc, ScannedFile, SourceRange, path_for_matching};
+
+/// Column names, in output order.
+const HEADER: [&str; 6] = ["path", "language", "kind", "container", "name", "loc"];
+
+/// Accessor names the C# analyzer appends to a property, indexer or event name.
+const CSHARP_ACCESSORS: [&str; 5] = ["get", "set", "init", "add", "remove"];
+
+/// Field separator and quoting for one flat-table flavor.
+#[derive(Clone, Copy)]
+pub(super) enum Delimited {
+    /// RFC 4180 CSV: fields with commas, quotes or line breaks are quoted.
+    Csv,
+    /// Tab-separated values: tabs and line breaks inside fields become spaces.
+    Tsv,
+}
+
+impl Delimited {
+    fn separator(self) -> &'static str {
+        match self {
+            Self::Csv => ",",
+            Self::Tsv => "\t",
+        }
+    }
+
+    fn field(self, value: &str) -> Cow<'_, str> {
+        match self {
+            Self::Csv if value.contains([',', '"', '\n', '\r']) => {
+                Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+            }
+            Self::Tsv if value.contains(['\t', '\n', '\r']) => {
+                Cow::Owned(value.replace(['\t', '\n', '\r'], " "))
+            }
+            Self::Csv | Self::Tsv => Cow::Borrowed(value),
+        }
+    }
+}
+
+/// One table row: a function, type, impl block or constant of a scanned file.
+struct Row {
+    kind: &'static str,
+    container: String,
+    name: String,
+    loc: usize,
+    range: SourceRange,
+}
+
+/// Renders one row per item of every file, files in path order and items grouped by kind.
+pub(super) fn render(files: &[ScannedFile], format: Delimited) -> String {
+    let separator = format.separator();
+    let mut lines = vec![HEADER.join(separator)];
+    for file in files {
+        let path = path_for_matching(&file.relative_path);
+        let language = file.language.display_name();
+        for row in rows(&file.summary, file.language) {
+            let loc = row.loc.to_string();
+            let fields = [
+                path.as_str(),
+                language,
+                row.kind,
+                &row.container,
+                &row.name,
+                &loc,
+            ];
+            lines.push(
+                fields
+                    .iter()
+                    .map(|value| format.field(value))
+                    .collect::<Vec<_>>()
+                    .join(separator),
+            );
+        }
+    }
+    lines.join("\n")
+}
+
+fn rows(summary: &FileLocSummary, language: Language) -> Vec<Row> {
+    let named: [(&str, &[NamedLoc]); 9] = [
+        ("fn", &summary.file_scope_functions),
+        ("test", &summary.test_functions),
+        ("struct", &summary.struct_defs),
+        ("enum", &summary.enum_defs),
+        ("trait", &summary.trait_defs),
+        ("const", &summary.consts),
+        ("static", &summary.statics),
+        ("delegate", &summary.delegate_defs),
+        ("event", &summary.event_defs),
+    ];
+
+    let mut rows = Vec::new();
+    for (kind, entries) in &named[..2] {
+        rows.extend(named_rows(kind, entries, language));
+    }
+    rows.extend(sorted(summary.impl_methods.iter().map(|entry| Row {
+        kind: "impl method",
+        container: match &entry.trait_name {
+            Some(trait_name) => format!("{trait_name} for {}", entry.impl_target),
+            None => entry.impl_target.clone(),
+        },
+        name: entry.method_name.clone(),
+        loc: entry.loc,
+        range: entry.range,
+    })));
+    rows.extend(sorted(summary.trait_methods.iter().map(|entry| Row {
+        kind: "trait method",
+        container: entry.trait_name.clone(),
+        name: entry.method_name.clone(),
+        loc: entry.loc,
+        range: entry.range,
+    })));
+    for (kind, entries) in &named[2..5] {
+        rows.extend(named_rows(kind, entries, language));
+    }
+    rows.extend(sorted(summary.impl_blocks.iter().map(|block| Row {
+        kind: "impl block",
+        container: String::new(),
+        name: block.display_name(),
+        loc: block.loc,
+        range: block.range,
+    })));
+    for (kind, entries) in &named[5..] {
+        rows.extend(named_rows(kind, entries, language));
+    }
+    rows
+}
+
+fn named_rows(kind: &'static str, entries: &[NamedLoc], language: Language) -> Vec<Row> {
+    sorted(entries.iter().map(|entry| {
+        let (container, name) = split_qualified(&entry.name, language);
+        Row {
+            kind,
+            container,
+            name,
+            loc: entry.loc,
+            range: entry.range,
+        }
+    }))
+}
+
+/// Orders rows of one kind by where they appear in the file.
+fn sorted(rows: impl Iterator<Item = Row>) -> Vec<Row> {
+    let mut rows = rows.collect::<Vec<_>>();
+    rows.sort_by(|a, b| {
+        (a.range.start_line, a.range.start_column)
+            .cmp(&(b.range.start_line, b.range.start_column))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    rows
+}
+
+/// Splits a qualified name into its container and last segment: `lexer::parse` in Rust,
+/// `Shop.Domain.Customer` in C#. Separators inside generic arguments are not split at, and a C#
+/// accessor keeps its property (`Shop.Customer` and `Name.get`), as impl-method rows show it.
+fn split_qualified(name: &str, language: Language) -> (String, String) {
+    let separator = match language {
+        Language::Csharp => ".",
+        Language::Rust | Language::Auto => "::",
+    };
+    let mut depth = 0usize;
+    let mut split_points = Vec::new();
+    for (index, character) in name.char_indices() {
+        match character {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && name[index..].starts_with(separator) => split_points.push(index),
+            _ => {}
+        }
+    }
+    let split_at = match split_points.as_slice() {
+        [.., property, last]
+            if language == Language::Csharp
+                && CSHARP_ACCESSORS.contains(&name[last + separator.len()..].trim()) =>
+        {
+            Some(*property)
+        }
+        [.., last] => Some(*last),
+        [] => None,
+    };
+    match split_at {
+        Some(index) => (
+            name[..index].trim().to_string(),
+            name[index + separator.len()..].trim().to_string(),
+        ),
+        None => (String::new(), name.to_string()),
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use crate::language::Language;
+use crate::scanner::{FileLocSummary, ScannedFile, SourceRange, TopLimit, path_for_matching};
+
+/// Aggregate file count and LOC for a single language in the report.
+pub(super) struct LanguageTotals {
+    pub language: Language,
+    pub files: usize,
+    pub total_loc: usize,
+}
+
+/// Groups scanned files by language, keeping the configured language order.
+pub(super) fn by_language(languages: &[Language], files: &[ScannedFile]) -> Vec<LanguageTotals> {
+    languages
+        .iter()
+        .map(|&language| {
+            let (count, total_loc) = files
+                .iter()
+                .filter(|file| file.language == language)
+                .fold((0, 0), |(count, loc), file| {
+                    (count + 1, loc + file.summary.total_loc)
+                });
+            LanguageTotals {
+                language,
+                files: count,
+                total_loc,
+            }
+        })
+        .collect()
+}
+
+/// Aggregate file count and LOC for a single C# namespace across the report.
+pub(super) struct NamespaceTotals {
+    pub name: String,
+    pub files: usize,
+    pub total_loc: usize,
+}
+
+/// Sums namespace LOC across files, sorted by name. Nested namespaces include their children's
+/// lines in the parent's total, like directories do.
+pub(super) fn by_namespace(files: &[ScannedFile]) -> Vec<NamespaceTotals> {
+    let mut totals: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for file in files {
+        let mut seen = Vec::new();
+        for namespace in &file.summary.namespaces {
+            let entry = totals.entry(namespace.name.as_str()).or_default();
+            if !seen.contains(&namespace.name.as_str()) {
+                seen.push(namespace.name.as_str());
+                entry.0 += 1;
+            }
+            entry.1 += namespace.loc;
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(name, (files, total_loc))| NamespaceTotals {
+            name: name.to_string(),
+            files,
+            total_loc,
+        })
+        .collect()
+}
+
+/// One entry of the scan-wide ranking of the longest functions.
+pub(super) struct RankedFunction<'a> {
+    pub path: String,
+    pub name: &'a str,
+    pub loc: usize,
+    pub range: SourceRange,
+}
+
+/// Ranks every file's `top_functions` together, longest first.
+///
+/// Each file already keeps its own `top` longest functions, so the global `top` are among them.
+pub(super) fn longest_functions(files: &[ScannedFile], top: TopLimit) -> Vec<RankedFunction<'_>> {
+    let mut ranked = files
+        .iter()
+        .flat_map(|file| {
+            let path = file.relative_path.to_string_lossy();
+            file.summary
+                .top_functions
+                .iter()
+                .map(move |entry| RankedFunction {
+                    path: path.to_string(),
+                    name: &entry.name,
+                    loc: entry.loc,
+                    range: entry.range,
+                })
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| {
+        b.loc
+            .cmp(&a.loc)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.name.cmp(b.name))
+    });
+    top.truncate(&mut ranked);
+    ranked
+}
+
+/// File count, LOC and longest function of everything below one directory.
+#[derive(Clone, Debug, Default)]
+pub(super) struct DirectoryTotals {
+    pub files: usize,
+    pub total_loc: usize,
+    /// `(name, loc, file path)` of the longest function, if any file has one.
+    pub largest_function: Option<(String, usize, String)>,
+}
+
+impl DirectoryTotals {
+    /// Folds one file at `path` into the totals.
+    pub fn add(&mut self, path: &str, summary: &FileLocSummary) {
+        self.files += 1;
+        self.total_loc += summary.total_loc;
+        // `top_functions` is ranked, so its first entry is the file's longest function.
+        if let Some(longest) = summary.top_functions.first()
+            && self
+                .largest_function
+                .as_ref()
+                .is_none_or(|(_, loc, _)| longest.loc > *loc)
+        {
+            self.largest_function = Some((longest.name.clone(), longest.loc, path.to_string()));
+        }
+    }
+}
+
+/// Totals for every scanned file together: the rollup of the scan root.
+pub(super) fn overall(files: &[ScannedFile]) -> DirectoryTotals {
+    files
+        .iter()
+        .fold(DirectoryTotals::default(), |mut totals, file| {
+            totals.add(&path_for_matching(&file.relative_path), &file.summary);
+            totals
+        })
+}
+
+/// Totals for every directory that contains a scanned file, keyed by `/`-separated path.
+pub(super) fn by_directory(files: &[ScannedFile]) -> BTreeMap<String, DirectoryTotals> {
+    let mut directories: BTreeMap<String, DirectoryTotals> = BTreeMap::new();
+    for file in files {
+        let path = path_for_matching(&file.relative_path);
+        let mut end = 0;
+        while let Some(offset) = path[end..].find('/') {
+            end += offset;
+            directories
+                .entry(path[..end].to_string())
+                .or_default()
+                .add(&path, &file.summary);
+            end += 1;
+        }
+    }
+    directories
+}
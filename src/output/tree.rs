@@ -1,36 +1,73 @@
 use std::collections::BTreeMap;
+use std::path::Path;
 
-use crate::scanner::{FileLocSummary, RootKind, ScannedFile, ScannerConfig};
+use crate::scanner::{FileLocSummary, NamedLoc, RootKind, ScannedFile, ScannerConfig, TopLimit};
 
+use super::OffenderFilter;
+use super::offenders::function_entries;
+use super::totals::{self, DirectoryTotals};
+use super::types::MergedType;
+
+/// Renders `files` as a directory tree. Directory rollups always cover every file in
+/// `scanned_files`, also in offender modes where `files` holds only the offenders.
 #[must_use]
-pub fn render(config: &ScannerConfig, files: &[ScannedFile], filter_active: bool) -> String {
-    let lines = match config.root_kind() {
-        RootKind::File => render_file_root(config, files, filter_active),
-        RootKind::Directory => render_directory_root(config, files, filter_active),
+pub fn render(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    scanned_files: &[ScannedFile],
+    merged_types: &[MergedType],
+    files_with_errors: &[&ScannedFile],
+    offender_filter: Option<&OffenderFilter>,
+) -> String {
+    let mut lines = match config.root_kind() {
+        RootKind::File => render_file_root(config, files, offender_filter),
+        RootKind::Directory => render_directory_root(config, files, scanned_files, offender_filter),
     };
 
+    if !merged_types.is_empty() {
+        render_partial_types(config, merged_types, &mut lines);
+    }
+
+    if !files_with_errors.is_empty() {
+        render_files_with_errors(config, files_with_errors, &mut lines);
+    }
+
     lines.join("\n")
 }
 
 fn render_file_root(
     config: &ScannerConfig,
     files: &[ScannedFile],
-    filter_active: bool,
+    offender_filter: Option<&OffenderFilter>,
 ) -> Vec<String> {
+    let filter_active = offender_filter.is_some();
     if let Some(file) = files.first() {
-        vec![format!(
+        let source_path = config.source_path(&file.relative_path);
+        let source_path = source_path.to_string_lossy();
+        let mut lines = vec![format!(
             ". {} ({})",
             config.root_label(),
-            format_summary(&file.summary)
-        )]
+            format_summary(&file.summary, &source_path, filter_active)
+        )];
+        if let Some(filter) = offender_filter {
+            render_offending_functions(
+                &file.summary,
+                &source_path,
+                "",
+                config.show_modules,
+                filter,
+                &mut lines,
+            );
+        }
+        if config.show_modules {
+            render_modules(&file.summary.modules, None, "", &mut lines);
+        }
+        lines
     } else {
         let message = if filter_active {
             "no files exceeded configured LOC limits".to_string()
         } else {
-            format!(
-                "no files matched language {}",
-                config.language.display_name()
-            )
+            format!("no files matched language {}", config.language_label())
         };
         vec![format!(". {} ({message})", config.root_label())]
     }
@@ -39,18 +76,20 @@ fn render_file_root(
 fn render_directory_root(
     config: &ScannerConfig,
     files: &[ScannedFile],
-    filter_active: bool,
+    scanned_files: &[ScannedFile],
+    offender_filter: Option<&OffenderFilter>,
 ) -> Vec<String> {
+    let filter_active = offender_filter.is_some();
     let mut lines = Vec::new();
-    lines.push(format!(". {}/", config.root_label()));
 
     if files.is_empty() {
+        lines.push(format!(". {}/", config.root_label()));
         if filter_active {
             lines.push("└── no files exceeded configured LOC limits".to_string());
         } else {
             lines.push(format!(
                 "└── no files matched language {}",
-                config.language.display_name()
+                config.language_label()
             ));
         }
         return lines;
@@ -60,54 +99,356 @@ fn render_directory_root(
     for entry in files {
         tree.insert(
             &entry.relative_path.components().collect::<Vec<_>>(),
+            &config.source_path(&entry.relative_path).to_string_lossy(),
             &entry.summary,
         );
     }
     tree.sort();
-    tree.render("", &mut lines);
+    tree.totals = totals::overall(scanned_files);
+    tree.attach_rollups(&totals::by_directory(scanned_files), "");
+    lines.push(format!(
+        ". {}/ ({})",
+        config.root_label(),
+        format_rollup(&tree.totals)
+    ));
+    let options = RenderOptions {
+        show_modules: config.show_modules,
+        offender_filter,
+        max_depth: config.max_depth,
+    };
+    tree.render("", 1, options, &mut lines);
+
+    if config.languages.len() > 1 {
+        render_language_totals(config, files, &mut lines);
+    }
+    render_namespace_totals(files, &mut lines);
+    render_longest_functions(config, files, &mut lines);
 
     lines
 }
 
-pub(crate) fn format_summary(summary: &FileLocSummary) -> String {
+fn render_language_totals(config: &ScannerConfig, files: &[ScannedFile], lines: &mut Vec<String>) {
+    let entries = totals::by_language(&config.languages, files);
+    lines.push(String::new());
+    lines.push("totals by language".to_string());
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let connector = if index + 1 == total {
+            "└──"
+        } else {
+            "├──"
+        };
+        lines.push(format!(
+            "{connector} {}: {} files, {} loc",
+            entry.language.display_name(),
+            entry.files,
+            entry.total_loc
+        ));
+    }
+}
+
+fn render_longest_functions(
+    config: &ScannerConfig,
+    files: &[ScannedFile],
+    lines: &mut Vec<String>,
+) {
+    let entries = totals::longest_functions(files, config.top);
+    if entries.is_empty() {
+        return;
+    }
+    lines.push(String::new());
+    lines.push(match config.top {
+        TopLimit::Count(count) => format!("longest functions (top {count})"),
+        TopLimit::All => "longest functions".to_string(),
+    });
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let connector = if index + 1 == total {
+            "└──"
+        } else {
+            "├──"
+        };
+        let source_path = config.source_path(Path::new(&entry.path));
+        lines.push(format!(
+            "{connector} {} ({} loc) at {}:{}",
+            entry.name,
+            entry.loc,
+            source_path.display(),
+            entry.range.start_line
+        ));
+    }
+}
+
+fn render_namespace_totals(files: &[ScannedFile], lines: &mut Vec<String>) {
+    let entries = totals::by_namespace(files);
+    if entries.is_empty() {
+        return;
+    }
+    lines.push(String::new());
+    lines.push("totals by namespace".to_string());
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let connector = if index + 1 == total {
+            "└──"
+        } else {
+            "├──"
+        };
+        lines.push(format!(
+            "{connector} {}: {} files, {} loc",
+            entry.name, entry.files, entry.total_loc
+        ));
+    }
+}
+
+fn render_partial_types(
+    config: &ScannerConfig,
+    merged_types: &[MergedType],
+    lines: &mut Vec<String>,
+) {
+    lines.push(String::new());
+    lines.push("partial types".to_string());
+    let total = merged_types.len();
+    for (index, merged_type) in merged_types.iter().enumerate() {
+        let is_last = index + 1 == total;
+        let connector = if is_last { "└──" } else { "├──" };
+        let prefix = if is_last { "    " } else { "│   " };
+        let members = if merged_type.members.is_empty() {
+            "none".to_string()
+        } else {
+            merged_type
+                .members
+                .iter()
+                .map(|member| format!("{} ({})", member.name, member.loc))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        lines.push(format!(
+            "{connector} {} ({} loc in {} files; members: {members})",
+            merged_type.name,
+            merged_type.total_loc,
+            merged_type.parts.len()
+        ));
+        let part_total = merged_type.parts.len();
+        for (part_index, part) in merged_type.parts.iter().enumerate() {
+            let part_connector = if part_index + 1 == part_total {
+                "└──"
+            } else {
+                "├──"
+            };
+            lines.push(format!(
+                "{prefix}{part_connector} {}:{} ({} loc)",
+                config.source_path(Path::new(&part.path)).display(),
+                part.range.start_line,
+                part.loc
+            ));
+        }
+    }
+}
+
+/// Lists each offending function of a filtered summary as a `path:line` reference, in source
+/// order, above the module lines rendered after it when `show_modules` is set. Complexity and
+/// nesting values over their limits are shown next to the LOC.
+fn render_offending_functions(
+    summary: &FileLocSummary,
+    source_path: &str,
+    prefix: &str,
+    show_modules: bool,
+    filter: &OffenderFilter,
+    lines: &mut Vec<String>,
+) {
+    let more_follow = show_modules
+        && summary
+            .modules
+            .iter()
+            .any(|module| !module.name.contains("::"));
+    let mut entries = function_entries(summary);
+    entries.sort_by(|a, b| {
+        (a.range.start_line, a.range.start_column)
+            .cmp(&(b.range.start_line, b.range.start_column))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let connector = if index + 1 == total && !more_follow {
+            "└──"
+        } else {
+            "├──"
+        };
+        let measures = std::iter::once(format!("{} loc", entry.loc))
+            .chain(
+                entry
+                    .metrics
+                    .map(|metrics| filter.exceeded_metrics(metrics))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(metric, actual, max)| format!("{metric} {actual} > {max}")),
+            )
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "{prefix}{connector} {} ({}) at {source_path}:{}",
+            entry.name,
+            measures.join(", "),
+            entry.range.start_line
+        ));
+    }
+}
+
+/// Lists every diagnostic, prefixed with a `path:line` reference when its line is known.
+fn render_files_with_errors(
+    config: &ScannerConfig,
+    files: &[&ScannedFile],
+    lines: &mut Vec<String>,
+) {
+    let entries = files
+        .iter()
+        .flat_map(|file| {
+            let source_path = config.source_path(&file.relative_path);
+            let source_path = source_path.to_string_lossy().into_owned();
+            file.diagnostics.iter().map(move |diagnostic| {
+                let reference = match diagnostic.line {
+                    Some(line) => format!("{source_path}:{line}"),
+                    None => source_path.clone(),
+                };
+                format!(
+                    "{reference}: {}: {}",
+                    diagnostic.kind.as_str(),
+                    diagnostic.message
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    lines.push(String::new());
+    lines.push("files with errors".to_string());
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let connector = if index + 1 == total {
+            "└──"
+        } else {
+            "├──"
+        };
+        lines.push(format!("{connector} {entry}"));
+    }
+}
+
+/// File line text. Outside offender mode it names the longest functions, each linked to its first
+/// line in `source_path`; in offender mode the offending functions get rows of their own (see
+/// `render_offending_functions`), so the text only adds the types over their limit.
+pub(crate) fn format_summary(
+    summary: &FileLocSummary,
+    source_path: &str,
+    filter_active: bool,
+) -> String {
+    if filter_active {
+        let types = type_offenders(summary);
+        if types.is_empty() {
+            return format!("{} loc", summary.total_loc);
+        }
+        return format!(
+            "{} loc; types over limit: {}",
+            summary.total_loc,
+            types.join(", ")
+        );
+    }
+
     let functions = if summary.top_functions.is_empty() {
         "none".to_string()
     } else {
         summary
             .top_functions
             .iter()
-            .map(|entry| format!("{} ({})", entry.name, entry.loc))
+            .map(|entry| {
+                format!(
+                    "{} ({}) at {source_path}:{}",
+                    entry.name, entry.loc, entry.range.start_line
+                )
+            })
             .collect::<Vec<_>>()
             .join(", ")
     };
-
     format!("{} loc; max fns loc: {}", summary.total_loc, functions)
 }
 
+fn type_offenders(summary: &FileLocSummary) -> Vec<String> {
+    summary
+        .struct_defs
+        .iter()
+        .chain(&summary.enum_defs)
+        .chain(&summary.trait_defs)
+        .map(|entry| format!("{} ({})", entry.name, entry.loc))
+        .chain(
+            summary
+                .impl_blocks
+                .iter()
+                .map(|block| format!("{} ({})", block.display_name(), block.loc)),
+        )
+        .collect()
+}
+
+fn format_rollup(totals: &DirectoryTotals) -> String {
+    let largest = totals.largest_function.as_ref().map_or_else(
+        || "none".to_string(),
+        |(name, loc, _)| format!("{name} ({loc})"),
+    );
+    format!(
+        "{} files, {} loc; largest fn: {largest}",
+        totals.files, totals.total_loc
+    )
+}
+
+#[derive(Clone, Copy)]
+struct RenderOptions<'a> {
+    show_modules: bool,
+    offender_filter: Option<&'a OffenderFilter>,
+    /// Directories at this depth are shown only as their rollup.
+    max_depth: Option<usize>,
+}
+
 #[derive(Default)]
 struct TreeNode {
     directories: BTreeMap<String, TreeNode>,
     files: Vec<FileEntry>,
+    totals: DirectoryTotals,
 }
 
 impl TreeNode {
-    fn insert(&mut self, components: &[std::path::Component<'_>], summary: &FileLocSummary) {
+    fn insert(
+        &mut self,
+        components: &[std::path::Component<'_>],
+        source_path: &str,
+        summary: &FileLocSummary,
+    ) {
         if let Some((first, rest)) = components.split_first() {
             let name = component_to_string(first);
             if rest.is_empty() {
                 self.files.push(FileEntry {
                     name,
+                    source_path: source_path.to_string(),
                     summary: summary.clone(),
                 });
             } else {
                 self.directories
                     .entry(name)
                     .or_default()
-                    .insert(rest, summary);
+                    .insert(rest, source_path, summary);
             }
         }
     }
 
+    /// Sets each directory's totals from `rollups`, keyed by `/`-separated path below `path`.
+    fn attach_rollups(&mut self, rollups: &BTreeMap<String, DirectoryTotals>, path: &str) {
+        for (name, child) in &mut self.directories {
+            let child_path = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{path}/{name}")
+            };
+            child.totals = rollups.get(&child_path).cloned().unwrap_or_default();
+            child.attach_rollups(rollups, &child_path);
+        }
+    }
+
     fn sort(&mut self) {
         self.files.sort_by(|a, b| a.name.cmp(&b.name));
         for child in self.directories.values_mut() {
@@ -115,7 +456,8 @@ impl TreeNode {
         }
     }
 
-    fn render(&self, prefix: &str, lines: &mut Vec<String>) {
+    /// Renders the children of a node at `depth` (the root's children are at depth 1).
+    fn render(&self, prefix: &str, depth: usize, options: RenderOptions, lines: &mut Vec<String>) {
         enum Entry<'a> {
             Dir(&'a str, &'a TreeNode),
             File(&'a FileEntry),
@@ -133,15 +475,20 @@ impl TreeNode {
         for (index, entry) in entries.into_iter().enumerate() {
             let is_last = index + 1 == total;
             let connector = if is_last { "└──" } else { "├──" };
+            let next_prefix = if is_last {
+                format!("{prefix}    ")
+            } else {
+                format!("{prefix}│   ")
+            };
             match entry {
                 Entry::Dir(name, child) => {
-                    lines.push(format!("{prefix}{connector} {name}/"));
-                    let next_prefix = if is_last {
-                        format!("{prefix}    ")
-                    } else {
-                        format!("{prefix}│   ")
-                    };
-                    child.render(&next_prefix, lines);
+                    lines.push(format!(
+                        "{prefix}{connector} {name}/ ({})",
+                        format_rollup(&child.totals)
+                    ));
+                    if options.max_depth.is_none_or(|max_depth| depth < max_depth) {
+                        child.render(&next_prefix, depth + 1, options, lines);
+                    }
                 }
                 Entry::File(file) => {
                     lines.push(format!(
@@ -149,17 +496,71 @@ impl TreeNode {
                         prefix,
                         connector,
                         file.name,
-                        format_summary(&file.summary)
+                        format_summary(
+                            &file.summary,
+                            &file.source_path,
+                            options.offender_filter.is_some(),
+                        )
                     ));
+                    if let Some(filter) = options.offender_filter {
+                        render_offending_functions(
+                            &file.summary,
+                            &file.source_path,
+                            &next_prefix,
+                            options.show_modules,
+                            filter,
+                            lines,
+                        );
+                    }
+                    if options.show_modules {
+                        render_modules(&file.summary.modules, None, &next_prefix, lines);
+                    }
                 }
             }
         }
     }
 }
 
+/// Renders the inline modules directly inside `parent` (the file itself when `None`), nesting
+/// each module's own children beneath it.
+fn render_modules(
+    modules: &[NamedLoc],
+    parent: Option<&str>,
+    prefix: &str,
+    lines: &mut Vec<String>,
+) {
+    let mut children = modules
+        .iter()
+        .filter(|module| module.name.rsplit_once("::").map(|(outer, _)| outer) == parent)
+        .collect::<Vec<_>>();
+    children.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let total = children.len();
+    for (index, module) in children.into_iter().enumerate() {
+        let is_last = index + 1 == total;
+        let connector = if is_last { "└──" } else { "├──" };
+        let short_name = module
+            .name
+            .rsplit_once("::")
+            .map_or(module.name.as_str(), |(_, name)| name);
+        lines.push(format!(
+            "{prefix}{connector} mod {short_name} ({} loc)",
+            module.loc
+        ));
+        let next_prefix = if is_last {
+            format!("{prefix}    ")
+        } else {
+            format!("{prefix}│   ")
+        };
+        render_modules(modules, Some(&module.name), &next_prefix, lines);
+    }
+}
+
 #[derive(Clone)]
 struct FileEntry {
     name: String,
+    /// Path as typed by the user, for `path:line` references.
+    source_path: String,
     summary: FileLocSummary,
 }
 
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use crate::language::Language;
+use crate::scanner::{ScannedFile, SourceRange, path_for_matching};
+
+/// A C# partial type with its declarations from every scanned file combined.
+#[derive(Clone, Debug)]
+pub(crate) struct MergedType {
+    pub name: String,
+    pub total_loc: usize,
+    pub parts: Vec<TypePart>,
+    /// Methods and accessors declared directly on the type, longest first.
+    pub members: Vec<TypeMember>,
+}
+
+/// One `partial` declaration of a merged type.
+#[derive(Clone, Debug)]
+pub(crate) struct TypePart {
+    pub path: String,
+    pub loc: usize,
+    pub range: SourceRange,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct TypeMember {
+    pub path: String,
+    pub name: String,
+    pub loc: usize,
+    pub range: SourceRange,
+}
+
+/// The C# partial types of a scan, merged once per run and shared by every consumer.
+#[derive(Clone, Debug, Default)]
+pub struct PartialTypes(Vec<MergedType>);
+
+impl PartialTypes {
+    /// Merges the `partial` declarations of `files`; see [`merge_partial_types`].
+    #[must_use]
+    pub fn merge(files: &[ScannedFile]) -> Self {
+        Self(merge_partial_types(files))
+    }
+
+    pub(crate) fn as_slice(&self) -> &[MergedType] {
+        &self.0
+    }
+
+    /// Whether `name` is split across files and therefore judged by its merged size.
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|merged_type| merged_type.name == name)
+    }
+}
+
+/// Groups `partial` type declarations by qualified name across `files`, sorted by name.
+///
+/// Only C# files take part, so a Rust type of the same name never attaches to a partial type.
+/// Types declared `partial` in a single place have nothing to merge and are left out; they
+/// are checked like any other type.
+fn merge_partial_types(files: &[ScannedFile]) -> Vec<MergedType> {
+    let files = files
+        .iter()
+        .filter(|file| file.language == Language::Csharp)
+        .collect::<Vec<_>>();
+    let mut merged: BTreeMap<&str, MergedType> = BTreeMap::new();
+    for file in &files {
+        let path = path_for_matching(&file.relative_path);
+        for declaration in &file.summary.partial_types {
+            let entry = merged
+                .entry(declaration.name.as_str())
+                .or_insert_with(|| MergedType {
+                    name: declaration.name.clone(),
+                    total_loc: 0,
+                    parts: Vec::new(),
+                    members: Vec::new(),
+                });
+            entry.total_loc += declaration.loc;
+            entry.parts.push(TypePart {
+                path: path.clone(),
+                loc: declaration.loc,
+                range: declaration.range,
+            });
+        }
+    }
+
+    for file in &files {
+        let path = path_for_matching(&file.relative_path);
+        let methods = file.summary.impl_methods.iter().map(|entry| {
+            (
+                entry.impl_target.as_str(),
+                entry.method_name.as_str(),
+                entry.loc,
+                entry.range,
+            )
+        });
+        let interface_methods = file.summary.trait_methods.iter().map(|entry| {
+            (
+                entry.trait_name.as_str(),
+                entry.method_name.as_str(),
+                entry.loc,
+                entry.range,
+            )
+        });
+        // Test methods carry one qualified name; the longest merged type prefix owns them.
+        let tests = file.summary.test_functions.iter().filter_map(|entry| {
+            let (owner, name) = owner_and_member(&entry.name, |owner| merged.contains_key(owner))?;
+            Some((owner, name, entry.loc, entry.range))
+        });
+        let members = methods
+            .chain(interface_methods)
+            .chain(tests)
+            .collect::<Vec<_>>();
+        for (owner, name, loc, range) in members {
+            if let Some(entry) = merged.get_mut(owner) {
+                entry.members.push(TypeMember {
+                    path: path.clone(),
+                    name: name.to_string(),
+                    loc,
+                    range,
+                });
+            }
+        }
+    }
+
+    merged
+        .into_values()
+        .filter(|merged_type| merged_type.parts.len() > 1)
+        .map(|mut merged_type| {
+            merged_type
+                .members
+                .sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.name.cmp(&b.name)));
+            merged_type
+        })
+        .collect()
+}
+
+/// Splits `Ns.Type.Member` at the longest leading type name `is_type` accepts.
+fn owner_and_member(name: &str, is_type: impl Fn(&str) -> bool) -> Option<(&str, &str)> {
+    name.char_indices()
+        .rev()
+        .filter(|&(_, character)| character == '.')
+        .map(|(index, _)| (&name[..index], &name[index + 1..]))
+        .find(|(owner, _)| is_type(owner))
+}
@@ -0,0 +1,306 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::scanner::{
+    FileLocSummary, FunctionMetrics, NamedLoc, ScannedFile, ScannerConfig, SourceRange,
+    path_for_matching,
+};
+
+use super::OffenderFilter;
+use super::offenders::{DEFAULT_RULE_NAME, FunctionEntry, file_offends, function_entries};
+use super::types::MergedType;
+
+/// Rule IDs with their short descriptions, shared by the CI-oriented report formats.
+pub(super) const RULES: [(&str, &str); 7] = [
+    ("file-too-long", "File exceeds the maximum LOC per file"),
+    (
+        "function-too-long",
+        "Function exceeds the maximum LOC per function",
+    ),
+    (
+        "function-too-complex",
+        "Function exceeds the maximum cyclomatic complexity",
+    ),
+    (
+        "function-hard-to-follow",
+        "Function exceeds the maximum cognitive complexity",
+    ),
+    (
+        "function-too-deeply-nested",
+        "Function nests blocks deeper than the maximum nesting depth",
+    ),
+    (
+        "type-too-long",
+        "Type definition exceeds the maximum LOC per type",
+    ),
+    (
+        "impl-block-too-long",
+        "Impl block exceeds the maximum LOC per impl block",
+    ),
+];
+
+/// One exceeded limit; an item over several limits yields one violation per limit.
+#[derive(Clone, Debug)]
+pub(super) struct Violation {
+    pub rule_id: &'static str,
+    pub message: String,
+    /// Scanned file path relative to the scan root, with `/` separators.
+    pub path: String,
+    pub range: SourceRange,
+    /// The offending function or type; `None` for the file itself.
+    pub item: Option<ViolatingItem>,
+    pub actual: usize,
+    pub threshold: usize,
+    /// Offender rule that set the threshold; `default` for the global thresholds.
+    pub rule: String,
+    /// Further declarations of a merged partial type, as `(path, range)`.
+    pub related: Vec<(String, SourceRange)>,
+}
+
+impl Violation {
+    /// The measure that exceeded its limit: `loc`, `cyclomatic`, `cognitive` or `nesting`.
+    pub fn metric(&self) -> &'static str {
+        match self.rule_id {
+            "function-too-complex" => "cyclomatic",
+            "function-hard-to-follow" => "cognitive",
+            "function-too-deeply-nested" => "nesting",
+            _ => "loc",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct ViolatingItem {
+    pub name: String,
+    /// SARIF logical location kind: `function`, `type` or `interface`.
+    pub kind: &'static str,
+}
+
+/// Lists every exceeded limit in offender-filtered `files` and `merged_types`, file by file.
+pub(super) fn collect(
+    files: &[ScannedFile],
+    merged_types: &[MergedType],
+    filter: &OffenderFilter,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for file in files {
+        let path = path_for_matching(&file.relative_path);
+        file_violations(file, &path, filter, &mut violations);
+        for entry in function_entries(&file.summary) {
+            function_violations(&entry, &path, file, filter, &mut violations);
+        }
+        type_violations(&file.summary, &path, filter, &mut violations);
+    }
+    for merged_type in merged_types {
+        violations.extend(merged_type_violation(merged_type, filter));
+    }
+    violations
+}
+
+/// Path of a scanned file as the user would type it, without `./` and with `/` separators.
+pub(super) fn display_path(config: &ScannerConfig, path: &str) -> String {
+    let path = config
+        .source_path(Path::new(path))
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect::<PathBuf>();
+    path_for_matching(&path)
+}
+
+fn file_violations(
+    file: &ScannedFile,
+    path: &str,
+    filter: &OffenderFilter,
+    violations: &mut Vec<Violation>,
+) {
+    if !file_offends(file, filter) {
+        return;
+    }
+    let limit = filter.limits_for(&file.relative_path).file;
+    let lines = &file.summary.lines;
+    violations.push(Violation {
+        rule_id: "file-too-long",
+        message: format!(
+            "File has {} LOC, above the limit of {} (rule `{}`)",
+            file.summary.total_loc, limit.max_loc, limit.rule
+        ),
+        path: path.to_string(),
+        range: SourceRange {
+            start_line: 1,
+            start_column: 1,
+            end_line: (lines.code + lines.comment + lines.blank).max(1),
+            end_column: 0,
+        },
+        item: None,
+        actual: file.summary.total_loc,
+        threshold: limit.max_loc,
+        rule: limit.rule.clone(),
+        related: Vec::new(),
+    });
+}
+
+fn function_violations(
+    entry: &FunctionEntry,
+    path: &str,
+    file: &ScannedFile,
+    filter: &OffenderFilter,
+    violations: &mut Vec<Violation>,
+) {
+    let violation = |rule_id, message, actual, threshold, rule: &str| Violation {
+        rule_id,
+        message,
+        path: path.to_string(),
+        range: entry.range,
+        item: Some(ViolatingItem {
+            name: entry.name.clone(),
+            kind: "function",
+        }),
+        actual,
+        threshold,
+        rule: rule.to_string(),
+        related: Vec::new(),
+    };
+
+    // An entry may pass the filter for one metric while the baseline still excuses the others.
+    let baselined = |metrics: Option<FunctionMetrics>| {
+        filter.baseline.as_ref().is_some_and(|baseline| {
+            let loc = if metrics.is_some() { 0 } else { entry.loc };
+            baseline.covers_function(path, Some(&entry.name), loc, metrics)
+        })
+    };
+
+    let limit = filter.limits_for(&file.relative_path).function;
+    if entry.loc > limit.max_loc && !baselined(None) {
+        violations.push(violation(
+            "function-too-long",
+            format!(
+                "Function `{}` has {} LOC, above the limit of {} (rule `{}`)",
+                entry.name, entry.loc, limit.max_loc, limit.rule
+            ),
+            entry.loc,
+            limit.max_loc,
+            &limit.rule,
+        ));
+    }
+
+    let Some(metrics) = entry.metrics.filter(|&metrics| !baselined(Some(metrics))) else {
+        return;
+    };
+    let checks = [
+        (
+            "function-too-complex",
+            "cyclomatic complexity",
+            metrics.cyclomatic,
+            filter.max_cyclomatic,
+        ),
+        (
+            "function-hard-to-follow",
+            "cognitive complexity",
+            metrics.cognitive,
+            filter.max_cognitive,
+        ),
+        (
+            "function-too-deeply-nested",
+            "nesting depth",
+            metrics.max_nesting,
+            filter.max_nesting,
+        ),
+    ];
+    for (rule_id, metric, actual, max) in checks {
+        if let Some(max) = max.filter(|&max| actual > max) {
+            violations.push(violation(
+                rule_id,
+                format!(
+                    "Function `{}` has {metric} {actual}, above the limit of {max}",
+                    entry.name
+                ),
+                actual,
+                max,
+                DEFAULT_RULE_NAME,
+            ));
+        }
+    }
+}
+
+fn type_violations(
+    summary: &FileLocSummary,
+    path: &str,
+    filter: &OffenderFilter,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(max_loc) = filter.max_loc_per_type {
+        let definitions: [(&[NamedLoc], &str); 3] = [
+            (&summary.struct_defs, "type"),
+            (&summary.enum_defs, "type"),
+            (&summary.trait_defs, "interface"),
+        ];
+        for (entries, kind) in definitions {
+            for entry in entries {
+                violations.push(Violation {
+                    rule_id: "type-too-long",
+                    message: format!(
+                        "Type `{}` has {} LOC, above the limit of {max_loc}",
+                        entry.name, entry.loc
+                    ),
+                    path: path.to_string(),
+                    range: entry.range,
+                    item: Some(ViolatingItem {
+                        name: entry.name.clone(),
+                        kind,
+                    }),
+                    actual: entry.loc,
+                    threshold: max_loc,
+                    rule: DEFAULT_RULE_NAME.to_string(),
+                    related: Vec::new(),
+                });
+            }
+        }
+    }
+
+    if let Some(max_loc) = filter.max_loc_per_impl_block {
+        for block in &summary.impl_blocks {
+            let name = block.display_name();
+            violations.push(Violation {
+                rule_id: "impl-block-too-long",
+                message: format!(
+                    "`{name}` has {} LOC, above the limit of {max_loc}",
+                    block.loc
+                ),
+                path: path.to_string(),
+                range: block.range,
+                item: Some(ViolatingItem { name, kind: "type" }),
+                actual: block.loc,
+                threshold: max_loc,
+                rule: DEFAULT_RULE_NAME.to_string(),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
+/// A partial type is reported at its first declaration, with the others as related locations.
+fn merged_type_violation(merged_type: &MergedType, filter: &OffenderFilter) -> Option<Violation> {
+    let (first, rest) = merged_type.parts.split_first()?;
+    let max_loc = filter.max_loc_per_type?;
+    Some(Violation {
+        rule_id: "type-too-long",
+        message: format!(
+            "Partial type `{}` has {} LOC across {} files, above the limit of {max_loc}",
+            merged_type.name,
+            merged_type.total_loc,
+            merged_type.parts.len()
+        ),
+        path: first.path.clone(),
+        range: first.range,
+        item: Some(ViolatingItem {
+            name: merged_type.name.clone(),
+            kind: "type",
+        }),
+        actual: merged_type.total_loc,
+        threshold: max_loc,
+        rule: DEFAULT_RULE_NAME.to_string(),
+        related: rest
+            .iter()
+            .map(|part| (part.path.clone(), part.range))
+            .collect(),
+    })
+}
@@ -0,0 +1,143 @@
+use syn::visit::Visit;
+use syn::{
+    Arm, BinOp, Block, Expr, ExprBinary, ExprBreak, ExprClosure, ExprContinue, ExprForLoop, ExprIf,
+    ExprLoop, ExprMatch, ExprTry, ExprWhile, Item,
+};
+
+use crate::scanner::summary::FunctionMetrics;
+
+/// Measures a function body; nested items are skipped because they are reported on their own.
+pub(super) fn function_metrics(block: &Block) -> FunctionMetrics {
+    let mut visitor = ComplexityVisitor::default();
+    visitor.visit_block(block);
+    FunctionMetrics {
+        cyclomatic: 1 + visitor.decisions,
+        cognitive: visitor.cognitive,
+        max_nesting: visitor.max_nesting,
+    }
+}
+
+#[derive(Default)]
+struct ComplexityVisitor {
+    decisions: usize,
+    cognitive: usize,
+    nesting: usize,
+    max_nesting: usize,
+}
+
+impl ComplexityVisitor {
+    /// Counts a structural break (`if`, `match`, loops) that is penalized for its nesting.
+    fn structural(&mut self) {
+        self.cognitive += 1 + self.nesting;
+    }
+
+    fn nested(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.nesting += 1;
+        self.max_nesting = self.max_nesting.max(self.nesting);
+        visit(self);
+        self.nesting -= 1;
+    }
+
+    fn visit_if_chain(&mut self, node: &ExprIf, is_else_if: bool) {
+        self.decisions += 1;
+        if is_else_if {
+            self.cognitive += 1;
+        } else {
+            self.structural();
+        }
+        self.visit_expr(&node.cond);
+        self.nested(|visitor| visitor.visit_block(&node.then_branch));
+
+        if let Some((_, else_branch)) = &node.else_branch {
+            if let Expr::If(else_if) = &**else_branch {
+                self.visit_if_chain(else_if, true);
+            } else {
+                self.cognitive += 1;
+                self.nested(|visitor| visitor.visit_expr(else_branch));
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ComplexityVisitor {
+    fn visit_item(&mut self, _node: &'ast Item) {}
+
+    fn visit_expr_if(&mut self, node: &'ast ExprIf) {
+        self.visit_if_chain(node, false);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast ExprMatch) {
+        self.decisions += node.arms.len().saturating_sub(1);
+        self.structural();
+        self.visit_expr(&node.expr);
+        self.nested(|visitor| {
+            for arm in &node.arms {
+                visitor.visit_arm(arm);
+            }
+        });
+    }
+
+    fn visit_arm(&mut self, node: &'ast Arm) {
+        if node.guard.is_some() {
+            self.decisions += 1;
+        }
+        syn::visit::visit_arm(self, node);
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast ExprForLoop) {
+        self.decisions += 1;
+        self.structural();
+        self.visit_expr(&node.expr);
+        self.nested(|visitor| visitor.visit_block(&node.body));
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast ExprWhile) {
+        self.decisions += 1;
+        self.structural();
+        self.visit_expr(&node.cond);
+        self.nested(|visitor| visitor.visit_block(&node.body));
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast ExprLoop) {
+        self.structural();
+        self.nested(|visitor| visitor.visit_block(&node.body));
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast ExprClosure) {
+        self.nested(|visitor| syn::visit::visit_expr_closure(visitor, node));
+    }
+
+    fn visit_expr_try(&mut self, node: &'ast ExprTry) {
+        self.decisions += 1;
+        syn::visit::visit_expr_try(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast ExprBinary) {
+        if matches!(node.op, BinOp::And(_) | BinOp::Or(_)) {
+            self.decisions += 1;
+            // A run of the same operator (`a && b && c`) is one break in flow.
+            let continues_run = matches!(
+                &*node.left,
+                Expr::Binary(left) if std::mem::discriminant(&left.op) == std::mem::discriminant(&node.op)
+            );
+            if !continues_run {
+                self.cognitive += 1;
+            }
+        }
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_break(&mut self, node: &'ast ExprBreak) {
+        if node.label.is_some() {
+            self.cognitive += 1;
+        }
+        syn::visit::visit_expr_break(self, node);
+    }
+
+    fn visit_expr_continue(&mut self, node: &'ast ExprContinue) {
+        if node.label.is_some() {
+            self.cognitive += 1;
+        }
+        syn::visit::visit_expr_continue(self, node);
+    }
+}
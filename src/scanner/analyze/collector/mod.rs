@@ -1,13 +1,23 @@
+mod complexity;
+mod test_detection;
 mod visit;
 
 use proc_macro2::Span;
 
-use crate::scanner::summary::{ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc};
+use crate::scanner::summary::{
+    CountMode, FunctionMetrics, ImplBlockLoc, ImplMethodLoc, LineCounts, NamedLoc, SourceRange,
+    TraitMethodLoc,
+};
 
 use super::CollectorParts;
+use super::lines::LineTable;
 
 pub(super) struct ItemCollector<'a> {
-    lines: Vec<&'a str>,
+    table: &'a LineTable,
+    count_mode: CountMode,
+    test_attributes: &'a [String],
+    /// Number of enclosing `#[cfg(test)]` modules.
+    test_module_depth: usize,
     file_scope_functions: Vec<NamedLoc>,
     impl_methods: Vec<ImplMethodLoc>,
     trait_methods: Vec<TraitMethodLoc>,
@@ -19,8 +29,11 @@ pub(super) struct ItemCollector<'a> {
     consts: Vec<NamedLoc>,
     statics: Vec<NamedLoc>,
     function_summaries: Vec<NamedLoc>,
+    modules: Vec<NamedLoc>,
     impl_stack: Vec<ImplContext>,
     trait_stack: Vec<String>,
+    /// Names of the enclosing inline modules, outermost first.
+    module_stack: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -30,9 +43,16 @@ struct ImplContext {
 }
 
 impl<'a> ItemCollector<'a> {
-    pub(super) fn new(source: &'a str) -> Self {
+    pub(super) fn new(
+        table: &'a LineTable,
+        count_mode: CountMode,
+        test_attributes: &'a [String],
+    ) -> Self {
         Self {
-            lines: source.lines().collect(),
+            table,
+            count_mode,
+            test_attributes,
+            test_module_depth: 0,
             file_scope_functions: Vec::new(),
             impl_methods: Vec::new(),
             trait_methods: Vec::new(),
@@ -44,8 +64,10 @@ impl<'a> ItemCollector<'a> {
             consts: Vec::new(),
             statics: Vec::new(),
             function_summaries: Vec::new(),
+            modules: Vec::new(),
             impl_stack: Vec::new(),
             trait_stack: Vec::new(),
+            module_stack: Vec::new(),
         }
     }
 
@@ -64,11 +86,24 @@ impl<'a> ItemCollector<'a> {
             consts: self.consts,
             statics: self.statics,
             function_summaries: self.function_summaries,
+            modules: self.modules,
+            namespaces: Vec::new(),
+            partial_types: Vec::new(),
         }
     }
 
-    fn record_loc(&self, span: Span) -> Option<usize> {
-        if self.lines.is_empty() {
+    /// Prefixes `name` with the enclosing inline module path, e.g. `lexer::parse`.
+    fn qualify(&self, name: &impl ToString) -> String {
+        let name = name.to_string();
+        if self.module_stack.is_empty() {
+            name
+        } else {
+            format!("{}::{name}", self.module_stack.join("::"))
+        }
+    }
+
+    fn record_loc(&self, span: Span) -> Option<(usize, LineCounts, SourceRange)> {
+        if self.table.is_empty() {
             return None;
         }
 
@@ -79,30 +114,39 @@ impl<'a> ItemCollector<'a> {
             return None;
         }
 
-        let start_index = start.saturating_sub(1);
-        let mut end_index = end.saturating_sub(1);
-        if end_index >= self.lines.len() {
-            end_index = self.lines.len().saturating_sub(1);
-        }
+        let lines = self
+            .table
+            .counts(start.saturating_sub(1), end.saturating_sub(1));
+        let loc = lines.select(self.count_mode);
+        // proc-macro2 columns are 0-based, and the end column points just past the span.
+        let range = SourceRange {
+            start_line: start,
+            start_column: span.start().column + 1,
+            end_line: end,
+            end_column: span.end().column,
+        };
 
-        if end_index < start_index {
-            return None;
+        if loc == 0 {
+            None
+        } else {
+            Some((loc, lines, range))
         }
-
-        let count = self.lines[start_index..=end_index]
-            .iter()
-            .filter(|line| !line.trim().is_empty())
-            .count();
-
-        if count == 0 { None } else { Some(count) }
     }
 
-    fn push_function_summary(&mut self, name: &str, span: Span) -> Option<usize> {
-        let loc = self.record_loc(span)?;
+    fn push_function_summary(
+        &mut self,
+        name: &str,
+        span: Span,
+        metrics: FunctionMetrics,
+    ) -> Option<(usize, LineCounts, SourceRange)> {
+        let (loc, lines, range) = self.record_loc(span)?;
         self.function_summaries.push(NamedLoc {
             name: name.to_string(),
             loc,
+            lines,
+            range,
+            metrics: Some(metrics),
         });
-        Some(loc)
+        Some((loc, lines, range))
     }
 }
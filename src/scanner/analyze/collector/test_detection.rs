@@ -0,0 +1,67 @@
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Meta, Path, Token};
+
+use super::ItemCollector;
+
+/// Attribute names whose last path segment marks a function as a test or benchmark.
+const TEST_ATTRIBUTES: [&str; 4] = ["test", "bench", "rstest", "test_case"];
+
+impl ItemCollector<'_> {
+    /// Whether code at the current position is inside a `#[cfg(test)]` module.
+    pub(super) fn in_test_module(&self) -> bool {
+        self.test_module_depth > 0
+    }
+
+    /// Whether the attributes mark an item as test-only code.
+    ///
+    /// Matches `#[test]`-style attributes by their last segment (so `#[tokio::test]` counts),
+    /// configured extra names by either their full path or last segment, and `#[cfg(test)]`.
+    pub(super) fn is_test_item(&self, attrs: &[Attribute]) -> bool {
+        attrs
+            .iter()
+            .any(|attr| is_cfg_test(attr) || self.is_test_attribute(attr.path()))
+    }
+
+    fn is_test_attribute(&self, path: &Path) -> bool {
+        let Some(last) = path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+        else {
+            return false;
+        };
+        if TEST_ATTRIBUTES.contains(&last.as_str()) {
+            return true;
+        }
+
+        let full = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        self.test_attributes
+            .iter()
+            .any(|name| *name == full || *name == last)
+    }
+}
+
+/// `#[cfg(test)]`, the conventional guard for unit test modules and helpers, also when `test`
+/// is nested in `all(..)` or `any(..)` such as `#[cfg(all(test, feature = "slow"))]`.
+pub(super) fn is_cfg_test(attr: &Attribute) -> bool {
+    attr.path().is_ident("cfg")
+        && attr
+            .parse_args::<Meta>()
+            .is_ok_and(|predicate| mentions_test(&predicate))
+}
+
+/// Whether a cfg predicate enables `test` directly or through `all`/`any`; `not(test)` does not.
+fn mentions_test(predicate: &Meta) -> bool {
+    match predicate {
+        Meta::Path(path) => path.is_ident("test"),
+        Meta::List(list) if list.path.is_ident("all") || list.path.is_ident("any") => list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .is_ok_and(|predicates| predicates.iter().any(mentions_test)),
+        Meta::List(_) | Meta::NameValue(_) => false,
+    }
+}
@@ -2,20 +2,33 @@ use quote::ToTokens;
 use syn::spanned::Spanned;
 use syn::visit::Visit;
 use syn::{
-    ImplItemFn, ItemConst, ItemEnum, ItemFn, ItemImpl, ItemStatic, ItemStruct, ItemTrait,
-    TraitItemFn,
+    ImplItemFn, ItemConst, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStatic, ItemStruct, ItemTrait,
+    TraitItemFn, Type,
 };
 
-use crate::scanner::summary::{ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc};
+use crate::scanner::summary::{
+    ImplBlockKind, ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc,
+};
 
+use super::complexity::function_metrics;
+use super::test_detection::is_cfg_test;
 use super::{ImplContext, ItemCollector};
 
 impl<'ast> Visit<'ast> for ItemCollector<'_> {
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
-        let fn_name = node.sig.ident.to_string();
-        if let Some(loc) = self.push_function_summary(&fn_name, node.span()) {
-            let is_test = node.attrs.iter().any(|attr| attr.path().is_ident("test"));
-            let entry = NamedLoc { name: fn_name, loc };
+        let fn_name = self.qualify(&node.sig.ident);
+        let metrics = function_metrics(&node.block);
+        if let Some((loc, lines, range)) =
+            self.push_function_summary(&fn_name, node.span(), metrics)
+        {
+            let is_test = self.in_test_module() || self.is_test_item(&node.attrs);
+            let entry = NamedLoc {
+                name: fn_name,
+                loc,
+                lines,
+                range,
+                metrics: Some(metrics),
+            };
             if is_test {
                 self.test_functions.push(entry);
             } else {
@@ -25,92 +38,170 @@ impl<'ast> Visit<'ast> for ItemCollector<'_> {
         syn::visit::visit_item_fn(self, node);
     }
 
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        // `mod name;` declarations live in their own files and are scanned there.
+        if node.content.is_none() {
+            return;
+        }
+
+        let name = self.qualify(&node.ident);
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
+            self.modules.push(NamedLoc {
+                name,
+                loc,
+                lines,
+                range,
+                metrics: None,
+            });
+        }
+
+        let is_test_module = node.attrs.iter().any(is_cfg_test);
+        if is_test_module {
+            self.test_module_depth += 1;
+        }
+        self.module_stack.push(node.ident.to_string());
+        syn::visit::visit_item_mod(self, node);
+        self.module_stack.pop();
+        if is_test_module {
+            self.test_module_depth -= 1;
+        }
+    }
+
     fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
-        if let Some(loc) = self.record_loc(node.span()) {
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
             self.struct_defs.push(NamedLoc {
-                name: node.ident.to_string(),
+                name: self.qualify(&node.ident),
                 loc,
+                lines,
+                range,
+                metrics: None,
             });
         }
         syn::visit::visit_item_struct(self, node);
     }
 
     fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
-        if let Some(loc) = self.record_loc(node.span()) {
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
             self.enum_defs.push(NamedLoc {
-                name: node.ident.to_string(),
+                name: self.qualify(&node.ident),
                 loc,
+                lines,
+                range,
+                metrics: None,
             });
         }
         syn::visit::visit_item_enum(self, node);
     }
 
     fn visit_item_const(&mut self, node: &'ast ItemConst) {
-        if let Some(loc) = self.record_loc(node.span()) {
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
             self.consts.push(NamedLoc {
-                name: node.ident.to_string(),
+                name: self.qualify(&node.ident),
                 loc,
+                lines,
+                range,
+                metrics: None,
             });
         }
         syn::visit::visit_item_const(self, node);
     }
 
     fn visit_item_static(&mut self, node: &'ast ItemStatic) {
-        if let Some(loc) = self.record_loc(node.span()) {
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
             self.statics.push(NamedLoc {
-                name: node.ident.to_string(),
+                name: self.qualify(&node.ident),
                 loc,
+                lines,
+                range,
+                metrics: None,
             });
         }
         syn::visit::visit_item_static(self, node);
     }
 
     fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
-        if let Some(loc) = self.record_loc(node.span()) {
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
             self.trait_defs.push(NamedLoc {
-                name: node.ident.to_string(),
+                name: self.qualify(&node.ident),
                 loc,
+                lines,
+                range,
+                metrics: None,
             });
         }
-        self.trait_stack.push(node.ident.to_string());
+        self.trait_stack.push(self.qualify(&node.ident));
         syn::visit::visit_item_trait(self, node);
         self.trait_stack.pop();
     }
 
     fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
-        if node.default.is_some()
+        if let Some(default) = &node.default
             && let Some(trait_name) = self.trait_stack.last().cloned()
         {
             let display = format!("trait {}::{}", trait_name, node.sig.ident);
-            if let Some(loc) = self.push_function_summary(&display, node.span()) {
-                self.trait_methods.push(TraitMethodLoc {
-                    trait_name,
-                    method_name: node.sig.ident.to_string(),
-                    loc,
-                });
+            let metrics = function_metrics(default);
+            if let Some((loc, lines, range)) =
+                self.push_function_summary(&display, node.span(), metrics)
+            {
+                if self.in_test_module() {
+                    self.test_functions.push(NamedLoc {
+                        name: format!("{}::{}", trait_name, node.sig.ident),
+                        loc,
+                        lines,
+                        range,
+                        metrics: Some(metrics),
+                    });
+                } else {
+                    self.trait_methods.push(TraitMethodLoc {
+                        trait_name,
+                        method_name: node.sig.ident.to_string(),
+                        loc,
+                        lines,
+                        range,
+                        metrics,
+                    });
+                }
             }
         }
         syn::visit::visit_trait_item_fn(self, node);
     }
 
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
-        let target = node.self_ty.to_token_stream().to_string();
+        let self_ty = node.self_ty.to_token_stream();
+        // Types named by a single segment are declared in (or imported into) this module; longer
+        // paths such as `super::Token` already say where the type lives.
+        let target = match &*node.self_ty {
+            Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+                self.qualify(&self_ty)
+            }
+            _ => self_ty.to_string(),
+        };
         let trait_name = node
             .trait_
             .as_ref()
             .map(|(_, path, _)| path.to_token_stream().to_string());
 
-        if let Some(loc) = self.record_loc(node.span()) {
+        if let Some((loc, lines, range)) = self.record_loc(node.span()) {
             self.impl_blocks.push(ImplBlockLoc {
                 target: target.clone(),
                 trait_name: trait_name.clone(),
+                kind: ImplBlockKind::Impl,
                 loc,
+                lines,
+                range,
             });
         }
 
         self.impl_stack.push(ImplContext { target, trait_name });
+        let is_test_impl = node.attrs.iter().any(is_cfg_test);
+        if is_test_impl {
+            self.test_module_depth += 1;
+        }
 
         syn::visit::visit_item_impl(self, node);
+        if is_test_impl {
+            self.test_module_depth -= 1;
+        }
         self.impl_stack.pop();
     }
 
@@ -122,13 +213,30 @@ impl<'ast> Visit<'ast> for ItemCollector<'_> {
                 }
                 None => format!("{}::{}", context.target, node.sig.ident),
             };
-            if let Some(loc) = self.push_function_summary(&display, node.span()) {
-                self.impl_methods.push(ImplMethodLoc {
-                    impl_target: context.target,
-                    trait_name: context.trait_name,
-                    method_name: node.sig.ident.to_string(),
-                    loc,
-                });
+            let is_test = self.in_test_module() || self.is_test_item(&node.attrs);
+            let metrics = function_metrics(&node.block);
+            if let Some((loc, lines, range)) =
+                self.push_function_summary(&display, node.span(), metrics)
+            {
+                if is_test {
+                    self.test_functions.push(NamedLoc {
+                        name: display,
+                        loc,
+                        lines,
+                        range,
+                        metrics: Some(metrics),
+                    });
+                } else {
+                    self.impl_methods.push(ImplMethodLoc {
+                        impl_target: context.target,
+                        trait_name: context.trait_name,
+                        method_name: node.sig.ident.to_string(),
+                        loc,
+                        lines,
+                        range,
+                        metrics,
+                    });
+                }
             }
         }
         syn::visit::visit_impl_item_fn(self, node);
@@ -1,12 +1,29 @@
 use tree_sitter::Node;
 
-use crate::scanner::summary::{ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc};
+use crate::scanner::summary::{
+    FunctionMetrics, ImplBlockKind, ImplBlockLoc, ImplMethodLoc, LineCounts, NamedLoc, SourceRange,
+    TraitMethodLoc,
+};
 
 use super::{ItemCollector, TypeContext};
+use crate::scanner::analyze::csharp::complexity::function_metrics;
 use crate::scanner::analyze::csharp::nodes::{
-    accessor_name, callable_name, first_identifier, has_modifier, name_for_node,
+    accessor_name, attribute_names, callable_name, first_identifier, has_modifier, name_for_node,
 };
 
+/// Method attributes from the xunit, nunit and mstest frameworks that mark a test.
+const TEST_METHOD_ATTRIBUTES: [&str; 6] = [
+    "Fact",
+    "Theory",
+    "Test",
+    "TestCase",
+    "TestMethod",
+    "DataTestMethod",
+];
+
+/// Type attributes from nunit and mstest that mark every member as test code.
+const TEST_CLASS_ATTRIBUTES: [&str; 2] = ["TestFixture", "TestClass"];
+
 impl ItemCollector<'_> {
     pub(super) fn push_struct_like(&mut self, node: Node<'_>, source: &[u8]) {
         if let Some(entry) = self.named_entry(node, source) {
@@ -24,8 +41,30 @@ impl ItemCollector<'_> {
 
     pub(super) fn push_type_context(&mut self, node: Node<'_>, source: &[u8], is_interface: bool) {
         self.push_impl_block(node, source, is_interface);
-        let name = name_for_node(node, source);
-        self.type_stack.push(TypeContext { name, is_interface });
+        let name = self.qualify(&name_for_node(node, source));
+        if has_modifier(node, source, "partial")
+            && let Some((loc, lines, range)) = self.line_counter.record(node)
+        {
+            self.partial_types.push(NamedLoc {
+                name: name.clone(),
+                loc,
+                lines,
+                range,
+                metrics: None,
+            });
+        }
+        let is_test = self
+            .type_stack
+            .last()
+            .is_some_and(|context| context.is_test)
+            || attribute_names(node, source)
+                .iter()
+                .any(|attribute| TEST_CLASS_ATTRIBUTES.contains(&attribute.as_str()));
+        self.type_stack.push(TypeContext {
+            name,
+            is_interface,
+            is_test,
+        });
         self.visit_children(node, source);
         self.type_stack.pop();
     }
@@ -43,36 +82,48 @@ impl ItemCollector<'_> {
     }
 
     pub(super) fn push_impl_block(&mut self, node: Node<'_>, source: &[u8], is_interface: bool) {
-        let Some(loc) = self.line_counter.record(node) else {
+        let Some((loc, lines, range)) = self.line_counter.record(node) else {
             return;
         };
         self.impl_blocks.push(ImplBlockLoc {
-            target: name_for_node(node, source),
-            trait_name: if is_interface {
-                Some("interface".to_string())
+            target: self.qualify(&name_for_node(node, source)),
+            trait_name: None,
+            kind: if is_interface {
+                ImplBlockKind::InterfaceBody
             } else {
-                None
+                ImplBlockKind::TypeBody
             },
             loc,
+            lines,
+            range,
         });
     }
 
     pub(super) fn named_entry(&self, node: Node<'_>, source: &[u8]) -> Option<NamedLoc> {
-        let loc = self.line_counter.record(node)?;
+        let (loc, lines, range) = self.line_counter.record(node)?;
         Some(NamedLoc {
-            name: name_for_node(node, source),
+            name: self.qualify(&name_for_node(node, source)),
             loc,
+            lines,
+            range,
+            metrics: None,
         })
     }
 
     pub(super) fn push_field(&mut self, node: Node<'_>, source: &[u8]) {
-        let Some(loc) = self.line_counter.record(node) else {
+        let Some((loc, lines, range)) = self.line_counter.record(node) else {
             return;
         };
         let Some(name) = first_identifier(node, source) else {
             return;
         };
-        let entry = NamedLoc { name, loc };
+        let entry = NamedLoc {
+            name: self.qualify(&name),
+            loc,
+            lines,
+            range,
+            metrics: None,
+        };
         if has_modifier(node, source, "const") {
             self.consts.push(entry);
         } else if has_modifier(node, source, "static") {
@@ -91,35 +142,29 @@ impl ItemCollector<'_> {
             return;
         }
 
-        let Some(loc) = self.line_counter.record(node) else {
+        let Some((loc, lines, range)) = self.line_counter.record(node) else {
             return;
         };
-        let name = callable_name(node, source);
-        self.function_summaries.push(NamedLoc {
-            name: name.clone(),
+        let is_test = self.in_test_code()
+            || attribute_names(node, source)
+                .iter()
+                .any(|attribute| TEST_METHOD_ATTRIBUTES.contains(&attribute.as_str()));
+        self.record_callable(
+            callable_name(node, source),
             loc,
-        });
+            lines,
+            range,
+            function_metrics(node),
+            is_test,
+        );
 
-        if let Some(context) = self.type_stack.last() {
-            if context.is_interface {
-                self.trait_methods.push(TraitMethodLoc {
-                    trait_name: context.name.clone(),
-                    method_name: name,
-                    loc,
-                });
-            } else {
-                self.impl_methods.push(ImplMethodLoc {
-                    impl_target: context.name.clone(),
-                    trait_name: None,
-                    method_name: name,
-                    loc,
-                });
-            }
-        } else {
-            self.file_scope_functions.push(NamedLoc { name, loc });
+        if is_test {
+            self.test_callable_depth += 1;
         }
-
         self.visit_children(node, source);
+        if is_test {
+            self.test_callable_depth -= 1;
+        }
     }
 
     pub(super) fn push_event(&mut self, node: Node<'_>, source: &[u8]) {
@@ -137,21 +182,83 @@ impl ItemCollector<'_> {
     }
 
     pub(super) fn push_local_function(&mut self, node: Node<'_>, source: &[u8]) {
-        let Some(loc) = self.line_counter.record(node) else {
+        let Some((loc, lines, range)) = self.line_counter.record(node) else {
             return;
         };
         let name = format!("local {}", name_for_node(node, source));
+        let metrics = Some(function_metrics(node));
         self.function_summaries.push(NamedLoc {
             name: name.clone(),
             loc,
+            lines,
+            range,
+            metrics,
         });
-        self.file_scope_functions.push(NamedLoc { name, loc });
+        let entry = NamedLoc {
+            name,
+            loc,
+            lines,
+            range,
+            metrics,
+        };
+        if self.in_test_code() {
+            self.test_functions.push(entry);
+        } else {
+            self.file_scope_functions.push(entry);
+        }
+        self.visit_children(node, source);
+    }
+
+    pub(super) fn push_namespace(&mut self, node: Node<'_>, source: &[u8]) {
+        let name = self.qualify(&name_for_node(node, source));
+        if let Some((loc, lines, range)) = self.line_counter.record(node) {
+            self.namespaces.push(NamedLoc {
+                name,
+                loc,
+                lines,
+                range,
+                metrics: None,
+            });
+        }
+        self.namespace_stack.push(name_for_node(node, source));
+        self.visit_children(node, source);
+        self.namespace_stack.pop();
+    }
+
+    /// A file-scoped namespace covers every declaration after it, which tree-sitter places
+    /// as siblings rather than children, so it is never popped.
+    pub(super) fn push_file_scoped_namespace(&mut self, node: Node<'_>, source: &[u8]) {
+        let name = self.qualify(&name_for_node(node, source));
+        let unit = node.parent().unwrap_or(node);
+        if let Some((loc, lines, range)) = self.line_counter.record_between(node, unit) {
+            self.namespaces.push(NamedLoc {
+                name,
+                loc,
+                lines,
+                range,
+                metrics: None,
+            });
+        }
+        self.namespace_stack.push(name_for_node(node, source));
         self.visit_children(node, source);
     }
 
     pub(super) fn push_top_level_statement(&mut self, node: Node<'_>) {
-        if let Some(loc) = self.line_counter.record(node) {
+        if let Some((loc, lines, range)) = self.line_counter.record(node) {
+            // All statements form one entry point, so their decision points add up.
+            let metrics = function_metrics(node);
+            self.top_level_statement_metrics.cyclomatic += metrics.cyclomatic - 1;
+            self.top_level_statement_metrics.cognitive += metrics.cognitive;
+            self.top_level_statement_metrics.max_nesting = self
+                .top_level_statement_metrics
+                .max_nesting
+                .max(metrics.max_nesting);
             self.top_level_statement_loc += loc;
+            self.top_level_statement_lines += lines;
+            self.top_level_statement_range = Some(
+                self.top_level_statement_range
+                    .map_or(range, |spanned| spanned.union(range)),
+            );
         }
     }
 
@@ -168,28 +275,57 @@ impl ItemCollector<'_> {
                 continue;
             }
 
-            let Some(loc) = self.line_counter.record(accessor) else {
+            let Some((loc, lines, range)) = self.line_counter.record(accessor) else {
                 continue;
             };
             recorded_any = true;
-            self.record_callable(accessor_name(accessor, source, &parent_name), loc);
+            self.record_callable(
+                accessor_name(accessor, source, &parent_name),
+                loc,
+                lines,
+                range,
+                function_metrics(accessor),
+                self.in_test_code(),
+            );
         }
 
         recorded_any
     }
 
-    fn record_callable(&mut self, name: String, loc: usize) {
+    fn record_callable(
+        &mut self,
+        name: String,
+        loc: usize,
+        lines: LineCounts,
+        range: SourceRange,
+        metrics: FunctionMetrics,
+        is_test: bool,
+    ) {
         self.function_summaries.push(NamedLoc {
-            name: name.clone(),
+            name: self.qualify(&name),
             loc,
+            lines,
+            range,
+            metrics: Some(metrics),
         });
 
-        if let Some(context) = self.type_stack.last() {
+        if is_test {
+            self.test_functions.push(NamedLoc {
+                name: self.qualify(&name),
+                loc,
+                lines,
+                range,
+                metrics: Some(metrics),
+            });
+        } else if let Some(context) = self.type_stack.last() {
             if context.is_interface {
                 self.trait_methods.push(TraitMethodLoc {
                     trait_name: context.name.clone(),
                     method_name: name,
                     loc,
+                    lines,
+                    range,
+                    metrics,
                 });
             } else {
                 self.impl_methods.push(ImplMethodLoc {
@@ -197,12 +333,39 @@ impl ItemCollector<'_> {
                     trait_name: None,
                     method_name: name,
                     loc,
+                    lines,
+                    range,
+                    metrics,
                 });
             }
         } else {
-            self.file_scope_functions.push(NamedLoc { name, loc });
+            self.file_scope_functions.push(NamedLoc {
+                name,
+                loc,
+                lines,
+                range,
+                metrics: Some(metrics),
+            });
         }
     }
+
+    /// Prefixes `name` with the enclosing type, or the enclosing namespaces outside any type.
+    fn qualify(&self, name: &str) -> String {
+        match self.type_stack.last() {
+            Some(context) => format!("{}.{name}", context.name),
+            None if self.namespace_stack.is_empty() => name.to_string(),
+            None => format!("{}.{name}", self.namespace_stack.join(".")),
+        }
+    }
+
+    /// Whether members visited now belong to a test type or test method.
+    fn in_test_code(&self) -> bool {
+        self.test_callable_depth > 0
+            || self
+                .type_stack
+                .last()
+                .is_some_and(|context| context.is_test)
+    }
 }
 
 fn find_accessors(node: Node<'_>) -> Option<Node<'_>> {
@@ -2,7 +2,9 @@ mod members;
 
 use tree_sitter::Node;
 
-use crate::scanner::summary::{ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc};
+use crate::scanner::summary::{
+    FunctionMetrics, ImplBlockLoc, ImplMethodLoc, LineCounts, NamedLoc, SourceRange, TraitMethodLoc,
+};
 
 use super::super::CollectorParts;
 use super::loc::LineCounter;
@@ -22,20 +24,34 @@ pub(super) struct ItemCollector<'a> {
     consts: Vec<NamedLoc>,
     statics: Vec<NamedLoc>,
     function_summaries: Vec<NamedLoc>,
+    namespaces: Vec<NamedLoc>,
+    partial_types: Vec<NamedLoc>,
     top_level_statement_loc: usize,
+    top_level_statement_lines: LineCounts,
+    top_level_statement_metrics: FunctionMetrics,
+    /// Lines from the first to the last top-level statement.
+    top_level_statement_range: Option<SourceRange>,
     type_stack: Vec<TypeContext>,
+    /// Enclosing namespace names as written (`Company.App`), outermost first. A file-scoped
+    /// namespace stays on the stack for the rest of the file.
+    namespace_stack: Vec<String>,
+    /// Number of enclosing test methods, so their local functions count as test code.
+    test_callable_depth: usize,
 }
 
 #[derive(Clone)]
 struct TypeContext {
+    /// Namespace-qualified name, including any enclosing types (`Company.App.Outer.Inner`).
     name: String,
     is_interface: bool,
+    /// Declared as, or nested in, a `[TestFixture]`/`[TestClass]` type.
+    is_test: bool,
 }
 
 impl<'a> ItemCollector<'a> {
-    pub(super) fn new(source: &'a str) -> Self {
+    pub(super) fn new(line_counter: LineCounter<'a>) -> Self {
         Self {
-            line_counter: LineCounter::new(source),
+            line_counter,
             file_scope_functions: Vec::new(),
             impl_methods: Vec::new(),
             trait_methods: Vec::new(),
@@ -49,16 +65,31 @@ impl<'a> ItemCollector<'a> {
             consts: Vec::new(),
             statics: Vec::new(),
             function_summaries: Vec::new(),
+            namespaces: Vec::new(),
+            partial_types: Vec::new(),
             top_level_statement_loc: 0,
+            top_level_statement_lines: LineCounts::default(),
+            top_level_statement_metrics: FunctionMetrics {
+                cyclomatic: 1,
+                ..FunctionMetrics::default()
+            },
+            top_level_statement_range: None,
             type_stack: Vec::new(),
+            namespace_stack: Vec::new(),
+            test_callable_depth: 0,
         }
     }
 
     pub(super) fn finish(mut self) -> CollectorParts {
-        if self.top_level_statement_loc > 0 {
+        if let Some(range) = self.top_level_statement_range
+            && self.top_level_statement_loc > 0
+        {
             let entry = NamedLoc {
                 name: "top-level statements".to_string(),
                 loc: self.top_level_statement_loc,
+                lines: self.top_level_statement_lines,
+                range,
+                metrics: Some(self.top_level_statement_metrics),
             };
             self.function_summaries.push(entry.clone());
             self.file_scope_functions.push(entry);
@@ -78,11 +109,22 @@ impl<'a> ItemCollector<'a> {
             consts: self.consts,
             statics: self.statics,
             function_summaries: self.function_summaries,
+            modules: Vec::new(),
+            namespaces: self.namespaces,
+            partial_types: self.partial_types,
         }
     }
 
     pub(super) fn visit(&mut self, node: Node<'_>, source: &[u8]) {
         let handled = match node.kind() {
+            "namespace_declaration" => {
+                self.push_namespace(node, source);
+                true
+            }
+            "file_scoped_namespace_declaration" => {
+                self.push_file_scoped_namespace(node, source);
+                true
+            }
             "class_declaration" | "struct_declaration" | "record_declaration" => {
                 self.push_struct_like(node, source);
                 true
@@ -0,0 +1,140 @@
+use tree_sitter::Node;
+
+use crate::scanner::summary::FunctionMetrics;
+
+/// Measures a callable; local functions are skipped because they are reported on their own.
+pub(super) fn function_metrics(node: Node<'_>) -> FunctionMetrics {
+    let mut counter = ComplexityCounter::default();
+    counter.visit_children(node, 0);
+    FunctionMetrics {
+        cyclomatic: 1 + counter.decisions,
+        cognitive: counter.cognitive,
+        max_nesting: counter.max_nesting,
+    }
+}
+
+#[derive(Default)]
+struct ComplexityCounter {
+    decisions: usize,
+    cognitive: usize,
+    max_nesting: usize,
+    /// Enclosing `try` bodies, which deepen nesting without weighing on cognitive complexity.
+    try_depth: usize,
+}
+
+impl ComplexityCounter {
+    fn visit(&mut self, node: Node<'_>, nesting: usize) {
+        if node.kind() != "local_function_statement" {
+            self.max_nesting = self.max_nesting.max(nesting + self.try_depth);
+        }
+        match node.kind() {
+            "local_function_statement" => {}
+            "try_statement" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    if node.child_by_field_name("body") == Some(child) {
+                        self.try_depth += 1;
+                        self.visit(child, nesting);
+                        self.try_depth -= 1;
+                    } else {
+                        self.visit(child, nesting);
+                    }
+                }
+            }
+            "if_statement" => self.visit_if(node, nesting, false),
+            "for_statement"
+            | "foreach_statement"
+            | "while_statement"
+            | "do_statement"
+            | "catch_clause"
+            | "conditional_expression" => {
+                self.decisions += 1;
+                self.cognitive += 1 + nesting;
+                self.visit_children(node, nesting + 1);
+            }
+            "switch_statement" => {
+                let sections = node
+                    .child_by_field_name("body")
+                    .map_or(0, |body| count_children(body, "switch_section"));
+                self.decisions += sections.saturating_sub(1);
+                self.cognitive += 1 + nesting;
+                self.visit_children(node, nesting + 1);
+            }
+            "switch_expression" => {
+                self.decisions += count_children(node, "switch_expression_arm").saturating_sub(1);
+                self.cognitive += 1 + nesting;
+                self.visit_children(node, nesting + 1);
+            }
+            "when_clause" => {
+                self.decisions += 1;
+                self.visit_children(node, nesting);
+            }
+            "lambda_expression" | "anonymous_method_expression" => {
+                self.visit_children(node, nesting + 1);
+            }
+            "goto_statement" => {
+                self.cognitive += 1;
+                self.visit_children(node, nesting);
+            }
+            "binary_expression" => {
+                if let Some(operator) = logical_operator(node) {
+                    self.decisions += 1;
+                    // A run of the same operator (`a && b && c`) is one break in flow.
+                    let continues_run = node
+                        .child_by_field_name("left")
+                        .is_some_and(|left| logical_operator(left) == Some(operator));
+                    if !continues_run {
+                        self.cognitive += 1;
+                    }
+                }
+                self.visit_children(node, nesting);
+            }
+            _ => self.visit_children(node, nesting),
+        }
+    }
+
+    fn visit_if(&mut self, node: Node<'_>, nesting: usize, is_else_if: bool) {
+        self.decisions += 1;
+        self.cognitive += if is_else_if { 1 } else { 1 + nesting };
+        if let Some(condition) = node.child_by_field_name("condition") {
+            self.visit(condition, nesting);
+        }
+        if let Some(consequence) = node.child_by_field_name("consequence") {
+            self.visit(consequence, nesting + 1);
+        }
+        if let Some(alternative) = node.child_by_field_name("alternative") {
+            if alternative.kind() == "if_statement" {
+                self.visit_if(alternative, nesting, true);
+            } else {
+                self.cognitive += 1;
+                self.visit(alternative, nesting + 1);
+            }
+        }
+    }
+
+    fn visit_children(&mut self, node: Node<'_>, nesting: usize) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child, nesting);
+        }
+    }
+}
+
+fn logical_operator(node: Node<'_>) -> Option<&'static str> {
+    if node.kind() != "binary_expression" {
+        return None;
+    }
+    match node.child_by_field_name("operator")?.kind() {
+        "&&" => Some("&&"),
+        "||" => Some("||"),
+        "??" => Some("??"),
+        _ => None,
+    }
+}
+
+fn count_children(node: Node<'_>, kind: &str) -> usize {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| child.kind() == kind)
+        .count()
+}
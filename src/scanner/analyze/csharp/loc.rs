@@ -1,34 +1,116 @@
 use tree_sitter::Node;
 
+use crate::scanner::summary::{CountMode, LineCounts, SourceRange};
+
+use super::super::lines::LineTable;
+
 pub(super) struct LineCounter<'a> {
-    lines: Vec<&'a str>,
+    source: &'a str,
+    table: &'a LineTable,
+    count_mode: CountMode,
 }
 
 impl<'a> LineCounter<'a> {
-    pub(super) fn new(source: &'a str) -> Self {
+    pub(super) fn new(source: &'a str, table: &'a LineTable, count_mode: CountMode) -> Self {
         Self {
-            lines: source.lines().collect(),
+            source,
+            table,
+            count_mode,
         }
     }
 
-    pub(super) fn record(&self, node: Node<'_>) -> Option<usize> {
-        if self.lines.is_empty() {
+    pub(super) fn record(&self, node: Node<'_>) -> Option<(usize, LineCounts, SourceRange)> {
+        self.record_between(node, node)
+    }
+
+    /// Counts from the start of `first` to the end of `last`, for spans that are not a single
+    /// node.
+    pub(super) fn record_between(
+        &self,
+        first: Node<'_>,
+        last: Node<'_>,
+    ) -> Option<(usize, LineCounts, SourceRange)> {
+        if self.table.is_empty() {
             return None;
         }
 
-        let start = node.start_position().row;
-        let mut end = node.end_position().row;
-        if end >= self.lines.len() {
-            end = self.lines.len().saturating_sub(1);
+        let start = first.start_position();
+        let (end_row, end_column) = self.end_of(last);
+        let lines = self.table.counts(start.row, end_row);
+        let loc = lines.select(self.count_mode);
+        let range = SourceRange {
+            start_line: start.row + 1,
+            start_column: self.char_column(first.start_byte(), start.column) + 1,
+            end_line: end_row + 1,
+            end_column,
+        };
+        if loc == 0 {
+            None
+        } else {
+            Some((loc, lines, range))
         }
-        if end < start {
-            return None;
+    }
+
+    /// Zero-based row and 1-based character column of the last character of `node`.
+    ///
+    /// tree-sitter columns are byte offsets pointing just past the node; a node that ends with
+    /// a newline (such as the compilation unit) ends on the previous row instead.
+    fn end_of(&self, node: Node<'_>) -> (usize, usize) {
+        let end = node.end_position();
+        if end.column > 0 || end.row <= node.start_position().row {
+            return (end.row, self.char_column(node.end_byte(), end.column));
         }
+        let before = self
+            .source
+            .get(..node.end_byte())
+            .unwrap_or_default()
+            .trim_end_matches(['\n', '\r']);
+        let line = before.rsplit('\n').next().unwrap_or_default();
+        (end.row - 1, line.chars().count())
+    }
 
-        let count = self.lines[start..=end]
-            .iter()
-            .filter(|line| !line.trim().is_empty())
-            .count();
-        if count == 0 { None } else { Some(count) }
+    fn char_column(&self, byte: usize, column: usize) -> usize {
+        char_column(self.source, byte, column)
+    }
+}
+
+/// Characters before `byte` on its line, given its byte `column`.
+pub(super) fn char_column(source: &str, byte: usize, column: usize) -> usize {
+    source
+        .get(byte.saturating_sub(column)..byte)
+        .map_or(column, |prefix| prefix.chars().count())
+}
+
+/// Classifies lines using the tree-sitter leaves; `comment` nodes never mark a line as code.
+pub(super) fn line_table(source: &str, root: Node<'_>) -> LineTable {
+    let mut code_rows = vec![false; source.lines().count()];
+    mark_code_leaves(root, &mut code_rows);
+    LineTable::from_code_rows(source, &code_rows)
+}
+
+fn mark_code_leaves(node: Node<'_>, code_rows: &mut [bool]) {
+    if node.kind() == "comment" {
+        return;
+    }
+    if node.child_count() == 0 {
+        // Zero-width leaves (e.g. missing tokens) carry no source text.
+        if node.start_byte() == node.end_byte() {
+            return;
+        }
+        let start = node.start_position().row;
+        let end_position = node.end_position();
+        let end = if end_position.column == 0 && end_position.row > start {
+            end_position.row - 1
+        } else {
+            end_position.row
+        };
+        for row in code_rows.iter_mut().take(end + 1).skip(start) {
+            *row = true;
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        mark_code_leaves(child, code_rows);
     }
 }
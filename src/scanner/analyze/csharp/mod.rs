@@ -1,32 +1,53 @@
 mod collector;
+mod complexity;
 mod loc;
 mod nodes;
 
 use anyhow::{Result, anyhow};
-use tree_sitter::Parser;
+use tree_sitter::{Node, Parser};
 
-use crate::scanner::summary::FileLocSummary;
+use crate::scanner::diagnostic::Diagnostic;
+use crate::scanner::summary::{FileLocSummary, TopLimit};
 
-use super::CollectorParts;
+use super::lines::LineTable;
+use super::{AnalyzeOptions, AnalyzedFile, CollectorParts};
 use collector::ItemCollector;
+use loc::LineCounter;
 
-pub(super) fn summarize_csharp_file(source: &str, total_loc: usize) -> Result<FileLocSummary> {
+pub(super) fn summarize_csharp_file(
+    source: &str,
+    options: AnalyzeOptions<'_>,
+) -> Result<AnalyzedFile> {
+    let count_mode = options.count_mode;
     let mut parser = Parser::new();
     parser
         .set_language(&tree_sitter_c_sharp::LANGUAGE.into())
         .map_err(|err| anyhow!("failed to load C# grammar: {err}"))?;
 
-    let tree = parser
-        .parse(source, None)
-        .ok_or_else(|| anyhow!("unable to parse C# source"))?;
+    let Some(tree) = parser.parse(source, None) else {
+        return Ok(AnalyzedFile::unparsed(
+            &LineTable::untokenized(source),
+            count_mode,
+            Diagnostic::parse_error("unable to parse C# source".to_string()),
+        ));
+    };
 
-    let mut collector = ItemCollector::new(source);
+    let table = loc::line_table(source, tree.root_node());
+    let lines = table.total();
+    let mut collector = ItemCollector::new(LineCounter::new(source, &table, count_mode));
     collector.visit(tree.root_node(), source.as_bytes());
     let parts = collector.finish();
 
-    Ok(FileLocSummary {
-        total_loc,
-        top_functions: top_functions(&parts),
+    // tree-sitter recovers from syntax errors, so keep the partial summary but flag the file.
+    let diagnostics = nodes::first_syntax_error(tree.root_node())
+        .map(|node| syntax_error(node, source))
+        .into_iter()
+        .collect();
+
+    let summary = FileLocSummary {
+        total_loc: lines.select(count_mode),
+        lines,
+        top_functions: top_functions(&parts, options.top),
         file_scope_functions: parts.file_scope_functions,
         impl_methods: parts.impl_methods,
         trait_methods: parts.trait_methods,
@@ -39,12 +60,47 @@ pub(super) fn summarize_csharp_file(source: &str, total_loc: usize) -> Result<Fi
         impl_blocks: parts.impl_blocks,
         consts: parts.consts,
         statics: parts.statics,
+        modules: parts.modules,
+        namespaces: parts.namespaces,
+        partial_types: parts.partial_types,
+    };
+
+    Ok(AnalyzedFile {
+        summary,
+        diagnostics,
     })
 }
 
-fn top_functions(parts: &CollectorParts) -> Vec<crate::scanner::NamedLoc> {
+/// Describes a tree-sitter error node. The grammar trails the newest C# releases, so the message
+/// quotes the offending line, letting users tell unsupported syntax from a real mistake.
+fn syntax_error(node: Node<'_>, source: &str) -> Diagnostic {
+    /// Longest excerpt of the offending line quoted in the message, in characters.
+    const MAX_EXCERPT: usize = 60;
+
+    let start = node.start_position();
+    let line = source.lines().nth(start.row).unwrap_or_default().trim();
+    let excerpt = if line.chars().count() > MAX_EXCERPT {
+        let shortened = line.chars().take(MAX_EXCERPT).collect::<String>();
+        format!("{shortened}...")
+    } else {
+        line.to_string()
+    };
+    let problem = if node.is_missing() {
+        format!("expected `{}`", node.kind())
+    } else {
+        "unexpected syntax".to_string()
+    };
+    Diagnostic::parse_error(format!(
+        "syntax error in C# source at line {}, column {}: {problem} in `{excerpt}`",
+        start.row + 1,
+        loc::char_column(source, node.start_byte(), start.column) + 1
+    ))
+    .at_line(start.row + 1)
+}
+
+fn top_functions(parts: &CollectorParts, top: TopLimit) -> Vec<crate::scanner::NamedLoc> {
     let mut top_candidates = parts.function_summaries.clone();
     top_candidates.sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.name.cmp(&b.name)));
-    top_candidates.truncate(3);
+    top.truncate(&mut top_candidates);
     top_candidates
 }
@@ -57,6 +57,42 @@ pub(super) fn name_for_node(node: Node<'_>, source: &[u8]) -> String {
     first_identifier(node, source).unwrap_or_else(|| node.kind().to_string())
 }
 
+/// Attribute names on a declaration, without namespace qualifiers or the `Attribute` suffix.
+pub(super) fn attribute_names(node: Node<'_>, source: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut cursor = node.walk();
+    for list in node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "attribute_list")
+    {
+        let mut list_cursor = list.walk();
+        for attribute in list
+            .children(&mut list_cursor)
+            .filter(|child| child.kind() == "attribute")
+        {
+            if let Some(name) = child_field_text(attribute, source, "name") {
+                let last = name.rsplit(['.', ':']).next().unwrap_or(name).trim();
+                names.push(last.strip_suffix("Attribute").unwrap_or(last).to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Returns the innermost node containing the first syntax error in document order.
+pub(super) fn first_syntax_error(node: Node<'_>) -> Option<Node<'_>> {
+    if !node.has_error() {
+        return None;
+    }
+    if node.is_error() || node.is_missing() {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .find_map(first_syntax_error)
+        .or(Some(node))
+}
+
 fn text<'a>(node: Node<'_>, source: &'a [u8]) -> Option<&'a str> {
     node.utf8_text(source).ok()
 }
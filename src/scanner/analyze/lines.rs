@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+
+use crate::scanner::summary::LineCounts;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LineKind {
+    Code,
+    Comment,
+    Blank,
+}
+
+/// Per-line classification of a source file into code, comment-only and blank lines.
+pub(super) struct LineTable {
+    kinds: Vec<LineKind>,
+}
+
+impl LineTable {
+    /// Builds a table where `code_rows[i]` marks whether 0-based line `i` holds a code token.
+    /// Non-blank lines without code tokens are treated as comments.
+    pub(super) fn from_code_rows(source: &str, code_rows: &[bool]) -> Self {
+        let kinds = source
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                if code_rows.get(row).copied().unwrap_or(false) {
+                    LineKind::Code
+                } else if line.trim().is_empty() {
+                    LineKind::Blank
+                } else {
+                    LineKind::Comment
+                }
+            })
+            .collect();
+        Self { kinds }
+    }
+
+    /// Classifies lines using the `proc-macro2` token stream; doc comments count as comments.
+    /// Falls back to treating every non-blank line as code when the source cannot be lexed.
+    pub(super) fn rust(source: &str) -> Self {
+        let Ok(stream) = TokenStream::from_str(source) else {
+            return Self::untokenized(source);
+        };
+        let lines = source.lines().collect::<Vec<_>>();
+        let mut code_rows = vec![false; lines.len()];
+        mark_rust_tokens(stream, &lines, &mut code_rows);
+        Self::from_code_rows(source, &code_rows)
+    }
+
+    /// Treats every non-blank line as code, for sources whose tokens are unavailable.
+    pub(super) fn untokenized(source: &str) -> Self {
+        let code_rows = source
+            .lines()
+            .map(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>();
+        Self::from_code_rows(source, &code_rows)
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Counts the lines in the inclusive 0-based row range, clamped to the file length.
+    pub(super) fn counts(&self, start_row: usize, end_row: usize) -> LineCounts {
+        let mut counts = LineCounts::default();
+        if self.kinds.is_empty() {
+            return counts;
+        }
+        let end_row = end_row.min(self.kinds.len() - 1);
+        if end_row < start_row {
+            return counts;
+        }
+        for kind in &self.kinds[start_row..=end_row] {
+            match kind {
+                LineKind::Code => counts.code += 1,
+                LineKind::Comment => counts.comment += 1,
+                LineKind::Blank => counts.blank += 1,
+            }
+        }
+        counts
+    }
+
+    pub(super) fn total(&self) -> LineCounts {
+        self.counts(0, self.kinds.len().saturating_sub(1))
+    }
+}
+
+fn mark_rust_tokens(stream: TokenStream, lines: &[&str], rows: &mut [bool]) {
+    let mut tokens = stream.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct)
+                if punct.as_char() == '#' && is_doc_comment(punct.span(), lines) =>
+            {
+                // `///` and `//!` lex as `#`, optional `!`, and a `[doc = ...]` group.
+                tokens.find(|next| {
+                    matches!(next, TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket)
+                });
+            }
+            TokenTree::Group(group) => {
+                mark_span(group.span_open(), rows);
+                mark_span(group.span_close(), rows);
+                mark_rust_tokens(group.stream(), lines, rows);
+            }
+            other => mark_span(other.span(), rows),
+        }
+    }
+}
+
+fn is_doc_comment(span: Span, lines: &[&str]) -> bool {
+    let start = span.start();
+    let Some(line) = start.line.checked_sub(1).and_then(|row| lines.get(row)) else {
+        return false;
+    };
+    let rest = line.chars().skip(start.column).collect::<String>();
+    rest.starts_with("//") || rest.starts_with("/*")
+}
+
+fn mark_span(span: Span, rows: &mut [bool]) {
+    let start = span.start().line;
+    let end = span.end().line;
+    if start == 0 || end < start {
+        return;
+    }
+    for row in (start - 1)..end.min(rows.len()) {
+        rows[row] = true;
+    }
+}
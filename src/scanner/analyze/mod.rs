@@ -1,5 +1,6 @@
 mod csharp;
 mod collector;
+mod lines;
 
 use std::fs;
 use std::path::Path;
@@ -8,22 +9,58 @@ use anyhow::{Context, Result};
 
 use crate::language::Language;
 
-use super::summary::{FileLocSummary, ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc};
+use super::diagnostic::Diagnostic;
+use super::summary::{
+    CountMode, FileLocSummary, ImplBlockLoc, ImplMethodLoc, NamedLoc, TopLimit, TraitMethodLoc,
+};
 use csharp::summarize_csharp_file;
 use collector::ItemCollector;
+use lines::LineTable;
 use syn::visit::Visit;
 
-pub fn analyze_file(language: Language, path: &Path) -> Result<FileLocSummary> {
-    let source = read_source(path)?;
+/// Summary of a single file plus any non-fatal problems found while analyzing it.
+pub struct AnalyzedFile {
+    pub summary: FileLocSummary,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl AnalyzedFile {
+    fn clean(summary: FileLocSummary) -> Self {
+        Self {
+            summary,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn unparsed(table: &LineTable, count_mode: CountMode, diagnostic: Diagnostic) -> Self {
+        let lines = table.total();
+        Self {
+            summary: FileLocSummary::unparsed(lines.select(count_mode), lines),
+            diagnostics: vec![diagnostic],
+        }
+    }
+}
 
-    let total_loc = source
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .count();
+/// Settings that shape how a single file is analyzed.
+#[derive(Clone, Copy, Debug)]
+pub struct AnalyzeOptions<'a> {
+    pub count_mode: CountMode,
+    /// Attribute names, beyond the built-in ones, that mark a Rust function as a test.
+    pub test_attributes: &'a [String],
+    /// How many of the file's longest functions to keep in `top_functions`.
+    pub top: TopLimit,
+}
+
+pub fn analyze_file(
+    language: Language,
+    path: &Path,
+    options: AnalyzeOptions<'_>,
+) -> Result<AnalyzedFile> {
+    let source = read_source(path)?;
 
     match language {
-        Language::Rust => summarize_rust_file(&source, total_loc),
-        Language::Csharp => summarize_csharp_file(&source, total_loc),
+        Language::Rust => Ok(summarize_rust_file(&source, options)),
+        Language::Csharp => summarize_csharp_file(&source, options),
         Language::Auto => unreachable!("scanner config resolves auto-detected language"),
     }
 }
@@ -33,18 +70,34 @@ fn read_source(path: &Path) -> Result<String> {
     Ok(String::from_utf8_lossy(&bytes).into_owned())
 }
 
-fn summarize_rust_file(source: &str, total_loc: usize) -> Result<FileLocSummary> {
-    let syntax = syn::parse_file(source).context("unable to parse Rust source")?;
-    let mut collector = ItemCollector::new(source);
+fn summarize_rust_file(source: &str, options: AnalyzeOptions<'_>) -> AnalyzedFile {
+    let count_mode = options.count_mode;
+    let table = LineTable::rust(source);
+    let syntax = match syn::parse_file(source) {
+        Ok(syntax) => syntax,
+        Err(error) => {
+            let start = error.span().start();
+            let message = format!(
+                "unable to parse Rust source at line {}, column {}: {error}",
+                start.line,
+                start.column + 1
+            );
+            let diagnostic = Diagnostic::parse_error(message).at_line(start.line);
+            return AnalyzedFile::unparsed(&table, count_mode, diagnostic);
+        }
+    };
+    let lines = table.total();
+    let mut collector = ItemCollector::new(&table, count_mode, options.test_attributes);
     collector.visit_file(&syntax);
     let parts = collector.finish();
 
     let mut top_candidates = parts.function_summaries.clone();
     top_candidates.sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.name.cmp(&b.name)));
-    top_candidates.truncate(3);
+    options.top.truncate(&mut top_candidates);
 
-    Ok(FileLocSummary {
-        total_loc,
+    AnalyzedFile::clean(FileLocSummary {
+        total_loc: lines.select(count_mode),
+        lines,
         top_functions: top_candidates,
         file_scope_functions: parts.file_scope_functions,
         impl_methods: parts.impl_methods,
@@ -58,6 +111,9 @@ fn summarize_rust_file(source: &str, total_loc: usize) -> Result<FileLocSummary>
         impl_blocks: parts.impl_blocks,
         consts: parts.consts,
         statics: parts.statics,
+        modules: parts.modules,
+        namespaces: parts.namespaces,
+        partial_types: parts.partial_types,
     })
 }
 
@@ -75,4 +131,7 @@ pub(super) struct CollectorParts {
     pub consts: Vec<NamedLoc>,
     pub statics: Vec<NamedLoc>,
     pub function_summaries: Vec<NamedLoc>,
+    pub modules: Vec<NamedLoc>,
+    pub namespaces: Vec<NamedLoc>,
+    pub partial_types: Vec<NamedLoc>,
 }
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use super::summary::{FileLocSummary, SourceRange};
+
+/// Files added or modified since a git ref, with the new-side line ranges of each hunk.
+#[derive(Clone, Debug)]
+pub struct ChangeSet {
+    pub reference: String,
+    /// Drop functions that do not intersect a changed hunk.
+    pub functions_only: bool,
+    /// Keyed by path relative to the scan root.
+    files: HashMap<PathBuf, Vec<SourceRange>>,
+}
+
+impl ChangeSet {
+    /// Runs `git diff` against `reference` in the local repository containing `directory`, and
+    /// adds untracked files that are not ignored as changed in full.
+    ///
+    /// Paths are reported relative to `directory`, and only files below it are considered.
+    ///
+    /// # Errors
+    /// Returns an error when git cannot be run, `directory` is not inside a work tree, or the
+    /// ref does not resolve.
+    pub fn since(reference: &str, directory: &Path, functions_only: bool) -> Result<Self> {
+        let diff = git(
+            directory,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-renames",
+                "--relative",
+                "--unified=0",
+                "--diff-filter=AM",
+                reference,
+                "--",
+            ],
+            &format!("git diff against '{reference}'"),
+        )?;
+        let untracked = git(
+            directory,
+            &[
+                "ls-files",
+                "--others",
+                "--exclude-standard",
+                "-z",
+                "--",
+                ".",
+            ],
+            "listing untracked files with git",
+        )?;
+
+        let mut files = parse_unified_diff(&diff);
+        for path in untracked.split('\0').filter(|path| !path.is_empty()) {
+            files.insert(
+                PathBuf::from(path),
+                vec![SourceRange {
+                    start_line: 1,
+                    end_line: usize::MAX,
+                    ..SourceRange::default()
+                }],
+            );
+        }
+
+        Ok(Self {
+            reference: reference.to_string(),
+            functions_only,
+            files,
+        })
+    }
+
+    /// Whether the file at `relative` (to the scan root) was added or modified.
+    #[must_use]
+    pub fn contains(&self, relative: &Path) -> bool {
+        self.files.contains_key(relative)
+    }
+
+    /// Drops function entries that do not overlap any changed hunk of the file.
+    pub(crate) fn retain_changed_functions(&self, relative: &Path, summary: &mut FileLocSummary) {
+        let hunks = self.files.get(relative).map_or(&[][..], Vec::as_slice);
+        let touched = |range: SourceRange| {
+            hunks
+                .iter()
+                .any(|hunk| range.intersects(hunk.start_line, hunk.end_line))
+        };
+
+        summary.top_functions.retain(|entry| touched(entry.range));
+        summary
+            .file_scope_functions
+            .retain(|entry| touched(entry.range));
+        summary.impl_methods.retain(|entry| touched(entry.range));
+        summary.trait_methods.retain(|entry| touched(entry.range));
+        summary.test_functions.retain(|entry| touched(entry.range));
+    }
+}
+
+/// Runs git with `args` in `directory` and returns its standard output; `action` describes the
+/// command in errors.
+fn git(directory: &Path, args: &[&str], action: &str) -> Result<String> {
+    let output = match Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            bail!("git was not found on PATH; --changed-since needs git installed")
+        }
+        Err(err) => return Err(err).context("failed to run git"),
+    };
+    if !output.status.success() {
+        bail!(
+            "{action} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Collects `+++ b/<path>` file headers and the new-side ranges of their `@@` hunks.
+fn parse_unified_diff(diff: &str) -> HashMap<PathBuf, Vec<SourceRange>> {
+    let mut files: HashMap<PathBuf, Vec<SourceRange>> = HashMap::new();
+    let mut current: Option<PathBuf> = None;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = header_path(path);
+            if let Some(path) = &current {
+                files.entry(path.clone()).or_default();
+            }
+        } else if let Some(header) = line.strip_prefix("@@ ")
+            && let Some(path) = &current
+            && let Some(range) = parse_hunk_header(header)
+        {
+            files.entry(path.clone()).or_default().push(range);
+        }
+    }
+
+    files
+}
+
+/// Turns the path of a `+++` header into a path relative to the diff root.
+///
+/// git ends the header with a tab when the path contains a space, and C-quotes paths holding
+/// quotes, backslashes or control characters. `/dev/null` (a deleted file) yields `None`.
+fn header_path(header: &str) -> Option<PathBuf> {
+    let header = header.strip_suffix('\t').unwrap_or(header);
+    let path = match header
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+    {
+        Some(quoted) => unquote(quoted)?,
+        None => header.to_string(),
+    };
+    path.strip_prefix("b/").map(PathBuf::from)
+}
+
+/// Reverses git's C-style quoting: backslash escapes and `\ooo` octal bytes.
+fn unquote(quoted: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut rest = quoted.bytes();
+    while let Some(byte) = rest.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        let escaped = match rest.next()? {
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b't' => b'\t',
+            b'n' => b'\n',
+            b'v' => 0x0b,
+            b'f' => 0x0c,
+            b'r' => b'\r',
+            digit @ b'0'..=b'3' => {
+                let mut value = digit - b'0';
+                for _ in 0..2 {
+                    let next = rest.next().filter(u8::is_ascii_digit)?;
+                    value = value * 8 + (next - b'0');
+                }
+                value
+            }
+            other => other,
+        };
+        bytes.push(escaped);
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Parses the `+start[,count]` part of a hunk header into a line range.
+///
+/// Pure deletions (`count == 0`) are attributed to the line the removal follows.
+fn parse_hunk_header(header: &str) -> Option<SourceRange> {
+    let new_side = header
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match new_side.split_once(',') {
+        Some((start, count)) => (start.parse::<usize>().ok()?, count.parse::<usize>().ok()?),
+        None => (new_side.parse::<usize>().ok()?, 1),
+    };
+
+    let start_line = start.max(1);
+    Some(SourceRange {
+        start_line,
+        end_line: start_line + count.saturating_sub(1),
+        ..SourceRange::default()
+    })
+}
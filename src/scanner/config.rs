@@ -9,18 +9,54 @@ use regex::Regex;
 use crate::cli::Cli;
 use crate::language::Language;
 
+use super::analyze::AnalyzeOptions;
+use super::changes::ChangeSet;
+use super::scan::excluded_by_patterns;
+use super::summary::{CountMode, TopLimit};
+
 #[derive(Clone, Debug)]
 pub struct ScannerConfig {
-    pub language: Language,
+    pub languages: Vec<Language>,
     canonical_root: PathBuf,
+    /// `--path` exactly as given, so reported locations resolve from the caller's directory.
+    display_root: PathBuf,
     pub git_ignore: bool,
     pub excludes: Vec<PathBuf>,
     pub include_path_regexes: Vec<Regex>,
     pub exclude_path_regexes: Vec<Regex>,
+    /// Where `excludes` and the path regexes are anchored.
+    pub pattern_bases: PatternBases,
+    /// Worker threads used for walking and analysis; `0` picks a count from available CPUs.
+    pub jobs: usize,
+    pub count_mode: CountMode,
+    /// Longest functions listed per file and across the whole scan.
+    pub top: TopLimit,
+    /// Extra attribute names that mark Rust functions as tests.
+    pub test_attributes: Vec<String>,
+    /// Nest inline modules under their file in the tree view.
+    pub show_modules: bool,
+    /// Deepest directory level expanded in the tree view; deeper levels show only rollups.
+    pub max_depth: Option<usize>,
+    /// Settings file merged into the CLI arguments, if one was found.
+    pub config_file: Option<PathBuf>,
+    /// Restricts the scan to files changed since a git ref (`--changed-since`).
+    pub changes: Option<ChangeSet>,
     root_kind: RootKind,
     root_label: String,
 }
 
+/// Prefixes that rebase scanned paths onto the directory the path patterns were written for.
+///
+/// Patterns from a config file above `--path` are relative to that file's directory, so the scan
+/// root's location below it is put in front of each relative path before matching. Patterns from
+/// the command line use an empty prefix.
+#[derive(Clone, Debug, Default)]
+pub struct PatternBases {
+    pub exclude: String,
+    pub include_path: String,
+    pub exclude_path: String,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RootKind {
     File,
@@ -43,13 +79,50 @@ impl ScannerConfig {
         &self.root_label
     }
 
+    /// Path of a scanned file as the user would type it, e.g. for `path:line` references.
+    #[must_use]
+    pub fn source_path(&self, relative_path: &Path) -> PathBuf {
+        match self.root_kind {
+            RootKind::File => self.display_root.clone(),
+            RootKind::Directory => self.display_root.join(relative_path),
+        }
+    }
+
+    /// Returns the configured language whose file extension matches `path`, if any.
+    #[must_use]
+    pub fn language_for(&self, path: &Path) -> Option<Language> {
+        self.languages
+            .iter()
+            .copied()
+            .find(|language| language.matches(path))
+    }
+
+    pub(crate) fn analyze_options(&self) -> AnalyzeOptions<'_> {
+        AnalyzeOptions {
+            count_mode: self.count_mode,
+            test_attributes: &self.test_attributes,
+            top: self.top,
+        }
+    }
+
+    /// Comma-separated display names of the configured languages.
+    #[must_use]
+    pub fn language_label(&self) -> String {
+        self.languages
+            .iter()
+            .map(Language::display_name)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     fn from_cli(cli: &Cli) -> Result<Self> {
-        let abs_path = if cli.path.is_absolute() {
-            cli.path.clone()
+        let path = cli.path.as_deref().context("--path is required")?;
+        let abs_path = if path.is_absolute() {
+            path.to_path_buf()
         } else {
             std::env::current_dir()
                 .context("failed to resolve current working directory")?
-                .join(&cli.path)
+                .join(path)
         };
 
         let canonical_root = abs_path
@@ -89,18 +162,49 @@ impl ScannerConfig {
 
         let include_path_regexes = compile_regexes(&cli.include_path, "include-path")?;
         let exclude_path_regexes = compile_regexes(&cli.exclude_path, "exclude-path")?;
-        let language = detect_language(cli.lang, &canonical_root, root_kind)?;
+        let changes = match &cli.changed_since {
+            Some(reference) => {
+                let directory = match root_kind {
+                    RootKind::Directory => canonical_root.as_path(),
+                    RootKind::File => canonical_root.parent().unwrap_or(&canonical_root),
+                };
+                Some(ChangeSet::since(
+                    reference,
+                    directory,
+                    cli.changed_functions_only,
+                )?)
+            }
+            None => None,
+        };
 
-        Ok(Self {
-            language,
+        let mut config = Self {
+            languages: Vec::new(),
             canonical_root,
+            display_root: path.to_path_buf(),
             git_ignore: cli.git_ignore_support,
             excludes,
             include_path_regexes,
             exclude_path_regexes,
+            pattern_bases: cli.pattern_bases.clone(),
+            jobs: cli.jobs.unwrap_or(0),
+            count_mode: cli.count_mode,
+            top: cli.top.unwrap_or_default(),
+            test_attributes: cli
+                .test_attribute
+                .iter()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            show_modules: cli.show_modules,
+            max_depth: cli.max_depth,
+            config_file: cli.config.clone(),
+            changes,
             root_kind,
             root_label,
-        })
+        };
+        // Detection honors the scan's skip rules, so it runs once those are in place.
+        config.languages = detect_languages(&cli.lang, &config)?;
+        Ok(config)
     }
 }
 
@@ -120,23 +224,26 @@ impl TryFrom<&Cli> for ScannerConfig {
     }
 }
 
-fn detect_language(requested: Language, root: &Path, root_kind: RootKind) -> Result<Language> {
-    if requested != Language::Auto {
-        return Ok(requested);
+fn detect_languages(requested: &[Language], config: &ScannerConfig) -> Result<Vec<Language>> {
+    if !requested.is_empty() && !requested.contains(&Language::Auto) {
+        let mut languages = requested.to_vec();
+        languages.sort();
+        languages.dedup();
+        return Ok(languages);
     }
 
-    match root_kind {
-        RootKind::File => detect_language_for_file(root),
-        RootKind::Directory => detect_language_for_directory(root),
+    match config.root_kind {
+        RootKind::File => detect_language_for_file(config.root()).map(|language| vec![language]),
+        RootKind::Directory => detect_languages_for_directory(config),
     }
 }
 
 fn detect_language_for_file(path: &Path) -> Result<Language> {
-    if Language::Rust.matches(path) {
-        return Ok(Language::Rust);
-    }
-    if Language::Csharp.matches(path) {
-        return Ok(Language::Csharp);
+    if let Some(language) = Language::SUPPORTED
+        .into_iter()
+        .find(|language| language.matches(path))
+    {
+        return Ok(language);
     }
 
     bail!(
@@ -145,7 +252,8 @@ fn detect_language_for_file(path: &Path) -> Result<Language> {
     );
 }
 
-fn detect_language_for_directory(root: &Path) -> Result<Language> {
+fn detect_languages_for_directory(config: &ScannerConfig) -> Result<Vec<Language>> {
+    let root = config.root();
     let has_cargo_toml = root.join("Cargo.toml").is_file();
     let has_dotnet_marker = fs::read_dir(root)
         .with_context(|| format!("failed to read directory {}", root.display()))?
@@ -163,9 +271,11 @@ fn detect_language_for_directory(root: &Path) -> Result<Language> {
     let mut csharp_files = 0usize;
     let mut builder = WalkBuilder::new(root);
     builder.hidden(false);
-    builder.git_ignore(false);
-    builder.git_global(false);
-    builder.git_exclude(false);
+    if !config.git_ignore {
+        builder.git_ignore(false);
+        builder.git_global(false);
+        builder.git_exclude(false);
+    }
 
     for entry in builder.build().flatten() {
         let path = entry.path();
@@ -173,43 +283,43 @@ fn detect_language_for_directory(root: &Path) -> Result<Language> {
             continue;
         }
 
-        if Language::Rust.matches(path) {
-            rust_files += 1;
-        } else if Language::Csharp.matches(path) {
-            let relative = path.strip_prefix(root).unwrap_or(path);
-            if !Language::Csharp.is_generated_path(relative) {
-                csharp_files += 1;
-            }
+        let Some(language) = Language::SUPPORTED
+            .into_iter()
+            .find(|language| language.matches(path))
+        else {
+            continue;
+        };
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if excluded_by_patterns(relative, language, config) {
+            continue;
+        }
+        match language {
+            Language::Rust => rust_files += 1,
+            Language::Csharp => csharp_files += 1,
+            Language::Auto => {}
         }
 
-        if rust_files >= 64 && csharp_files >= 64 {
+        if rust_files > 0 && csharp_files > 0 {
             break;
         }
     }
 
-    if has_cargo_toml && !has_dotnet_marker {
-        return Ok(Language::Rust);
-    }
-    if has_dotnet_marker && !has_cargo_toml {
-        return Ok(Language::Csharp);
+    let mut languages = Vec::new();
+    if has_cargo_toml || rust_files > 0 {
+        languages.push(Language::Rust);
     }
-    if rust_files > 0 && csharp_files == 0 {
-        return Ok(Language::Rust);
+    if has_dotnet_marker || csharp_files > 0 {
+        languages.push(Language::Csharp);
     }
-    if csharp_files > 0 && rust_files == 0 {
-        return Ok(Language::Csharp);
-    }
-    if rust_files > csharp_files {
-        return Ok(Language::Rust);
-    }
-    if csharp_files > rust_files {
-        return Ok(Language::Csharp);
+
+    if languages.is_empty() {
+        bail!(
+            "unable to auto-detect language for {}; pass --lang explicitly",
+            root.display()
+        )
     }
 
-    bail!(
-        "unable to auto-detect language for {}; pass --lang explicitly",
-        root.display()
-    )
+    Ok(languages)
 }
 
 fn compile_regexes(values: &[String], label: &str) -> Result<Vec<Regex>> {
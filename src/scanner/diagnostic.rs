@@ -0,0 +1,42 @@
+/// Problem encountered while analyzing a single file that did not abort the scan.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    /// 1-based line the problem was found at, when the parser reports one.
+    pub line: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiagnosticKind {
+    ParseError,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn parse_error(message: String) -> Self {
+        Self {
+            kind: DiagnosticKind::ParseError,
+            message,
+            line: None,
+        }
+    }
+
+    /// Attaches the 1-based line the problem was found at.
+    #[must_use]
+    pub fn at_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+}
+
+impl DiagnosticKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticKind::ParseError => "parse_error",
+        }
+    }
+}
@@ -1,8 +1,16 @@
 mod analyze;
+mod changes;
 mod config;
+mod diagnostic;
 mod scan;
 mod summary;
 
-pub use config::{RootKind, ScannerConfig};
+pub use changes::ChangeSet;
+pub use config::{PatternBases, RootKind, ScannerConfig};
+pub use diagnostic::{Diagnostic, DiagnosticKind};
+pub(crate) use scan::path_for_matching;
 pub use scan::{ScannedFile, scan};
-pub use summary::{FileLocSummary, ImplBlockLoc, ImplMethodLoc, NamedLoc, TraitMethodLoc};
+pub use summary::{
+    CountMode, FileLocSummary, FunctionMetrics, ImplBlockKind, ImplBlockLoc, ImplMethodLoc,
+    LineCounts, NamedLoc, SourceRange, TopLimit, TraitMethodLoc,
+};
@@ -1,17 +1,32 @@
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
 use anyhow::{Context, Result};
-use ignore::WalkBuilder;
+use ignore::{DirEntry, WalkBuilder, WalkState};
 use regex::Regex;
 
-use super::analyze::analyze_file;
+use crate::language::Language;
+
+use super::analyze::{AnalyzedFile, analyze_file};
 use super::config::{RootKind, ScannerConfig};
+use super::diagnostic::{Diagnostic, DiagnosticKind};
 use super::summary::FileLocSummary;
 
 #[derive(Clone, Debug)]
 pub struct ScannedFile {
     pub relative_path: PathBuf,
+    pub language: Language,
     pub summary: FileLocSummary,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ScannedFile {
+    #[must_use]
+    pub fn has_parse_error(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.kind == DiagnosticKind::ParseError)
+    }
 }
 
 /// Collects language-matching files under the configured root.
@@ -27,29 +42,26 @@ pub fn scan(config: &ScannerConfig) -> Result<Vec<ScannedFile>> {
 
 fn scan_file_root(config: &ScannerConfig) -> Result<Vec<ScannedFile>> {
     let path = config.root();
-    if !config.language.matches(path) {
+    let Some(language) = config.language_for(path) else {
         return Ok(Vec::new());
-    }
+    };
 
-    let summary = analyze_file(config.language, path)?;
     let relative = path
         .file_name()
         .map_or_else(|| PathBuf::from(path), PathBuf::from);
 
-    if should_skip(&relative, config) {
+    if should_skip(&relative, language, config) {
         return Ok(Vec::new());
     }
 
-    Ok(vec![ScannedFile {
-        relative_path: relative,
-        summary,
-    }])
+    let analyzed = analyze_file(language, path, config.analyze_options())?;
+    Ok(vec![scanned_file(relative, language, analyzed, config)])
 }
 
 fn scan_directory_root(config: &ScannerConfig) -> Result<Vec<ScannedFile>> {
     let mut builder = WalkBuilder::new(config.root());
-    builder.sort_by_file_name(std::cmp::Ord::cmp);
     builder.hidden(false);
+    builder.threads(config.jobs);
 
     if !config.git_ignore {
         builder.git_ignore(false);
@@ -57,55 +69,113 @@ fn scan_directory_root(config: &ScannerConfig) -> Result<Vec<ScannedFile>> {
         builder.git_exclude(false);
     }
 
-    let mut results = Vec::new();
-
-    for entry in builder.build() {
-        let entry = entry?;
-        let path = entry.path();
+    let (sender, receiver) = mpsc::channel();
+
+    builder.build_parallel().run(|| {
+        let sender = sender.clone();
+        // The receiver outlives the walk, so sends cannot fail.
+        Box::new(move |entry| match scan_entry(entry, config) {
+            Ok(None) => WalkState::Continue,
+            Ok(Some(file)) => {
+                let _ = sender.send(Ok(file));
+                WalkState::Continue
+            }
+            Err(error) => {
+                let _ = sender.send(Err(error));
+                WalkState::Quit
+            }
+        })
+    });
+    drop(sender);
+
+    // Worker threads finish in arbitrary order; sort to keep reports deterministic.
+    let mut results = receiver.into_iter().collect::<Result<Vec<_>>>()?;
+    results.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
 
-        if path.is_dir() {
-            continue;
-        }
+    Ok(results)
+}
 
-        let relative = match path.strip_prefix(config.root()) {
-            Ok(rel) => rel.to_path_buf(),
-            Err(_) => continue,
-        };
+fn scan_entry(
+    entry: Result<DirEntry, ignore::Error>,
+    config: &ScannerConfig,
+) -> Result<Option<ScannedFile>> {
+    let entry = entry?;
+    let path = entry.path();
 
-        if should_skip(&relative, config) {
-            continue;
-        }
+    if path.is_dir() {
+        return Ok(None);
+    }
 
-        if !config.language.matches(path) {
-            continue;
-        }
+    let Ok(relative) = path.strip_prefix(config.root()) else {
+        return Ok(None);
+    };
+    let relative = relative.to_path_buf();
 
-        let summary = analyze_file(config.language, path)
-            .with_context(|| format!("failed to analyze {}", path.display()))?;
+    let Some(language) = config.language_for(path) else {
+        return Ok(None);
+    };
 
-        results.push(ScannedFile {
-            relative_path: relative,
-            summary,
-        });
+    if should_skip(&relative, language, config) {
+        return Ok(None);
     }
 
-    results.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    let analyzed = analyze_file(language, path, config.analyze_options())
+        .with_context(|| format!("failed to analyze {}", path.display()))?;
 
-    Ok(results)
+    Ok(Some(scanned_file(relative, language, analyzed, config)))
 }
 
-fn should_skip(relative: &Path, config: &ScannerConfig) -> bool {
-    if config.language.is_generated_path(relative) {
-        return true;
+fn scanned_file(
+    relative_path: PathBuf,
+    language: Language,
+    analyzed: AnalyzedFile,
+    config: &ScannerConfig,
+) -> ScannedFile {
+    let mut summary = analyzed.summary;
+    if let Some(changes) = config
+        .changes
+        .as_ref()
+        .filter(|changes| changes.functions_only)
+    {
+        changes.retain_changed_functions(&relative_path, &mut summary);
     }
 
-    if config.excludes.iter().any(|ex| relative.starts_with(ex)) {
+    ScannedFile {
+        relative_path,
+        language,
+        summary,
+        diagnostics: analyzed.diagnostics,
+    }
+}
+
+fn should_skip(relative: &Path, language: Language, config: &ScannerConfig) -> bool {
+    excluded_by_patterns(relative, language, config)
+        || config
+            .changes
+            .as_ref()
+            .is_some_and(|changes| !changes.contains(relative))
+}
+
+/// Whether generated-file rules, `--exclude` or the path regexes drop `relative`; language
+/// detection applies the same rules so skipped files cannot enable a language.
+pub(super) fn excluded_by_patterns(
+    relative: &Path,
+    language: Language,
+    config: &ScannerConfig,
+) -> bool {
+    if language.is_generated_path(relative) {
         return true;
     }
 
     let match_target = path_for_matching(relative);
+    let bases = &config.pattern_bases;
+    let rebased = PathBuf::from(format!("{}{match_target}", bases.exclude));
+    if config.excludes.iter().any(|ex| rebased.starts_with(ex)) {
+        return true;
+    }
 
-    if matches_regexes(&match_target, &config.exclude_path_regexes) {
+    let exclude_target = format!("{}{match_target}", bases.exclude_path);
+    if matches_regexes(&exclude_target, &config.exclude_path_regexes) {
         return true;
     }
 
@@ -113,14 +183,16 @@ fn should_skip(relative: &Path, config: &ScannerConfig) -> bool {
         return false;
     }
 
-    !matches_regexes(&match_target, &config.include_path_regexes)
+    let include_target = format!("{}{match_target}", bases.include_path);
+    !matches_regexes(&include_target, &config.include_path_regexes)
 }
 
 fn matches_regexes(target: &str, regexes: &[Regex]) -> bool {
     regexes.iter().any(|re| re.is_match(target))
 }
 
-fn path_for_matching(relative: &Path) -> String {
+/// Relative path with `/` separators, as matched by path regexes and globs.
+pub(crate) fn path_for_matching(relative: &Path) -> String {
     let raw = relative.to_string_lossy();
     if std::path::MAIN_SEPARATOR == '/' {
         raw.into_owned()
@@ -1,6 +1,148 @@
+use std::ops::AddAssign;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Which line classes contribute to the LOC numbers used for thresholds and rankings.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum CountMode {
+    /// Lines containing at least one code token
+    Code,
+    /// Code lines plus comment-only lines (every non-blank line)
+    #[default]
+    #[value(name = "code+comments")]
+    CodeAndComments,
+    /// Every physical line, including blank ones
+    Physical,
+}
+
+impl CountMode {
+    #[must_use]
+    pub fn display_name(self) -> &'static str {
+        match self {
+            CountMode::Code => "code",
+            CountMode::CodeAndComments => "code+comments",
+            CountMode::Physical => "physical",
+        }
+    }
+}
+
+/// How many of the longest functions a ranking keeps (`--top`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TopLimit {
+    Count(usize),
+    All,
+}
+
+impl Default for TopLimit {
+    fn default() -> Self {
+        Self::Count(3)
+    }
+}
+
+impl TopLimit {
+    /// Drops the entries past the limit from an already ranked list.
+    pub fn truncate<T>(self, entries: &mut Vec<T>) {
+        if let Self::Count(count) = self {
+            entries.truncate(count);
+        }
+    }
+}
+
+impl FromStr for TopLimit {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case("all") {
+            return Ok(Self::All);
+        }
+        match value.parse::<usize>() {
+            Ok(0) => Err("value must be greater than zero or 'all'".to_string()),
+            Ok(count) => Ok(Self::Count(count)),
+            Err(err) => Err(format!(
+                "failed to parse '{value}' as a count or 'all': {err}"
+            )),
+        }
+    }
+}
+
+/// Breakdown of a line range into code, comment-only and blank lines.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LineCounts {
+    pub code: usize,
+    pub comment: usize,
+    pub blank: usize,
+}
+
+impl LineCounts {
+    /// Returns the LOC figure for the given counting mode.
+    #[must_use]
+    pub fn select(self, mode: CountMode) -> usize {
+        match mode {
+            CountMode::Code => self.code,
+            CountMode::CodeAndComments => self.code + self.comment,
+            CountMode::Physical => self.code + self.comment + self.blank,
+        }
+    }
+}
+
+impl AddAssign for LineCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+    }
+}
+
+/// Control-flow complexity of one function body.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FunctionMetrics {
+    /// One plus the decision points: branches, loops, `?`, `&&`/`||` and extra match arms.
+    pub cyclomatic: usize,
+    /// Breaks in linear flow, each weighted by how deeply it is nested.
+    pub cognitive: usize,
+    /// Deepest block nesting: branches, loops, closures and (in C#) `try` bodies.
+    pub max_nesting: usize,
+}
+
+/// 1-based, inclusive line range an item spans in its source file, with the columns (in
+/// characters, also 1-based and inclusive) of its first and last character.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SourceRange {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl SourceRange {
+    /// The smallest range covering both `self` and `other`.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let (start_line, start_column) =
+            (self.start_line, self.start_column).min((other.start_line, other.start_column));
+        let (end_line, end_column) =
+            (self.end_line, self.end_column).max((other.end_line, other.end_column));
+        Self {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// Whether any line in `start_line..=end_line` falls inside this range.
+    #[must_use]
+    pub fn intersects(self, start_line: usize, end_line: usize) -> bool {
+        self.start_line <= end_line && start_line <= self.end_line
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct FileLocSummary {
     pub total_loc: usize,
+    pub lines: LineCounts,
     pub top_functions: Vec<NamedLoc>,
     pub file_scope_functions: Vec<NamedLoc>,
     pub impl_methods: Vec<ImplMethodLoc>,
@@ -14,12 +156,48 @@ pub struct FileLocSummary {
     pub impl_blocks: Vec<ImplBlockLoc>,
     pub consts: Vec<NamedLoc>,
     pub statics: Vec<NamedLoc>,
+    /// Inline Rust modules, named by their path within the file (`lexer::tokens`).
+    pub modules: Vec<NamedLoc>,
+    /// C# namespaces declared in the file, named in full (`Company.App.Models`).
+    pub namespaces: Vec<NamedLoc>,
+    /// C# `partial` type declarations, merged across files by their qualified name.
+    pub partial_types: Vec<NamedLoc>,
+}
+
+impl FileLocSummary {
+    /// Summary for a file whose syntax could not be analyzed: only the raw line count is known.
+    pub(crate) fn unparsed(total_loc: usize, lines: LineCounts) -> Self {
+        Self {
+            total_loc,
+            lines,
+            top_functions: Vec::new(),
+            file_scope_functions: Vec::new(),
+            impl_methods: Vec::new(),
+            trait_methods: Vec::new(),
+            test_functions: Vec::new(),
+            struct_defs: Vec::new(),
+            enum_defs: Vec::new(),
+            trait_defs: Vec::new(),
+            delegate_defs: Vec::new(),
+            event_defs: Vec::new(),
+            impl_blocks: Vec::new(),
+            consts: Vec::new(),
+            statics: Vec::new(),
+            modules: Vec::new(),
+            namespaces: Vec::new(),
+            partial_types: Vec::new(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct NamedLoc {
     pub name: String,
     pub loc: usize,
+    pub lines: LineCounts,
+    pub range: SourceRange,
+    /// Set for function entries only.
+    pub metrics: Option<FunctionMetrics>,
 }
 
 #[derive(Clone, Debug)]
@@ -28,6 +206,9 @@ pub struct ImplMethodLoc {
     pub trait_name: Option<String>,
     pub method_name: String,
     pub loc: usize,
+    pub lines: LineCounts,
+    pub range: SourceRange,
+    pub metrics: FunctionMetrics,
 }
 
 #[derive(Clone, Debug)]
@@ -35,11 +216,43 @@ pub struct TraitMethodLoc {
     pub trait_name: String,
     pub method_name: String,
     pub loc: usize,
+    pub lines: LineCounts,
+    pub range: SourceRange,
+    pub metrics: FunctionMetrics,
 }
 
 #[derive(Clone, Debug)]
 pub struct ImplBlockLoc {
     pub target: String,
     pub trait_name: Option<String>,
+    pub kind: ImplBlockKind,
     pub loc: usize,
+    pub lines: LineCounts,
+    pub range: SourceRange,
+}
+
+impl ImplBlockLoc {
+    /// How the block is named in reports: `impl Trait for Target`, `impl Target`, or the
+    /// qualified type name for a C# type body.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        match (self.kind, &self.trait_name) {
+            (ImplBlockKind::Impl, Some(trait_name)) => {
+                format!("impl {trait_name} for {}", self.target)
+            }
+            (ImplBlockKind::Impl, None) => format!("impl {}", self.target),
+            (ImplBlockKind::TypeBody | ImplBlockKind::InterfaceBody, _) => self.target.clone(),
+        }
+    }
+}
+
+/// What an [`ImplBlockLoc`] was recorded from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImplBlockKind {
+    /// A Rust `impl` block, inherent or for a trait.
+    Impl,
+    /// The body of a C# class, struct or record, which also holds its methods.
+    TypeBody,
+    /// The body of a C# interface.
+    InterfaceBody,
 }
@@ -0,0 +1,307 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/test_proj";
+const EXIT_OFFENDERS_FOUND: i32 = 3;
+const EXIT_STALE_BASELINE: i32 = 4;
+
+#[test]
+fn write_baseline_records_every_offender() {
+    let baseline = temp_baseline("write");
+    let output = run(&[
+        "--write-baseline",
+        baseline.to_str().unwrap(),
+        "--offending-max-loc-per-file",
+        "120",
+        "--offending-max-loc-per-fn",
+        "30",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+
+    let recorded: Value =
+        serde_json::from_str(&fs::read_to_string(&baseline).expect("baseline not written"))
+            .expect("baseline is not json");
+    let entries = recorded["entries"].as_array().expect("entries array");
+    assert!(entries.iter().any(|entry| entry["path"] == "src/lib.rs"
+        && entry.get("function").is_none()
+        && entry["loc"] == 144));
+    assert!(
+        entries
+            .iter()
+            .any(|entry| entry["function"] == "build_full_report" && entry["loc"] == 79)
+    );
+    fs::remove_file(baseline).ok();
+}
+
+#[test]
+fn baselined_offenders_are_suppressed_in_check_mode() {
+    let baseline = temp_baseline("suppress");
+    let limits = [
+        "--offending-max-loc-per-file",
+        "120",
+        "--offending-max-loc-per-fn",
+        "30",
+    ];
+    let mut write_args = vec!["--write-baseline", baseline.to_str().unwrap()];
+    write_args.extend(limits);
+    assert!(run(&write_args).status.success());
+
+    let mut check_args = vec!["--check", "--baseline", baseline.to_str().unwrap()];
+    check_args.extend(limits);
+    let output = run(&check_args);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+
+    // Tightening the function limit surfaces functions missing from the baseline.
+    let mut tighter = vec!["--check", "--baseline", baseline.to_str().unwrap()];
+    tighter.extend([
+        "--offending-max-loc-per-file",
+        "120",
+        "--offending-max-loc-per-fn",
+        "5",
+        "--output-format",
+        "json",
+    ]);
+    let output = run(&tighter);
+    assert_eq!(output.status.code(), Some(EXIT_OFFENDERS_FOUND));
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+    let names = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|file| file["summary"]["file_scope_functions"].as_array().unwrap())
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert!(names.iter().any(|name| name == "main"));
+    assert!(!names.iter().any(|name| name == "build_full_report"));
+    assert!(!names.iter().any(|name| name == "compute_series_a"));
+    fs::remove_file(baseline).ok();
+}
+
+#[test]
+fn shrunk_entries_fail_a_stale_baseline_check() {
+    let baseline = temp_baseline("stale");
+    fs::write(
+        &baseline,
+        r#"{"entries":[{"path":"src/main.rs","function":"build_full_report","loc":500}]}"#,
+    )
+    .unwrap();
+
+    let output = run(&[
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--fail-on-stale-baseline",
+        "--offending-max-loc-per-file",
+        "120",
+        "--offending-max-loc-per-fn",
+        "30",
+    ]);
+    assert_eq!(output.status.code(), Some(EXIT_STALE_BASELINE));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("baseline is stale: 1 recorded item(s)"),
+        "got: {stderr}"
+    );
+    fs::remove_file(baseline).ok();
+}
+
+#[test]
+fn baseline_suppresses_trait_default_and_csharp_methods() {
+    let baseline = temp_baseline("qualified");
+    let limits = [
+        "--path",
+        "tests/baseline_proj",
+        "--offending-max-loc-per-file",
+        "100",
+        "--offending-max-loc-per-fn",
+        "3",
+    ];
+    let write = Command::new(BIN)
+        .args(limits)
+        .args(["--write-baseline", baseline.to_str().unwrap()])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(write.status.success(), "{write:?}");
+
+    let output = Command::new(BIN)
+        .args(limits)
+        .args(["--check", "--baseline", baseline.to_str().unwrap()])
+        .output()
+        .expect("failed to run loc-checker");
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.contains("no files exceeded configured LOC limits"),
+        "got: {stdout}"
+    );
+    assert!(!stdout.contains("Report::render"));
+    assert!(!stdout.contains("Billing.Service.Total"));
+    fs::remove_file(baseline).ok();
+}
+
+#[test]
+fn baseline_records_type_and_impl_block_offenders() {
+    let baseline = temp_baseline("types");
+    let rust = [
+        "--path",
+        "tests/types_proj",
+        "--offending-max-loc-per-type",
+        "5",
+        "--offending-max-loc-per-impl-block",
+        "5",
+    ];
+    let partial = [
+        "--path",
+        "tests/csharp_partial_proj",
+        "--offending-max-loc-per-type",
+        "10",
+    ];
+    for limits in [&rust[..], &partial[..]] {
+        let run_with = |extra: &[&str]| {
+            Command::new(BIN)
+                .args(limits)
+                .args([
+                    "--offending-max-loc-per-file",
+                    "1000",
+                    "--offending-max-loc-per-fn",
+                    "1000",
+                ])
+                .args(extra)
+                .output()
+                .expect("failed to run loc-checker")
+        };
+        let write = run_with(&["--write-baseline", baseline.to_str().unwrap()]);
+        assert!(write.status.success(), "{write:?}");
+        let check = run_with(&[
+            "--check",
+            "--fail-on-stale-baseline",
+            "--baseline",
+            baseline.to_str().unwrap(),
+        ]);
+        assert_eq!(check.status.code(), Some(0), "{check:?}");
+    }
+
+    // The last baseline written holds the merged partial type under its first declaration.
+    let recorded: Value =
+        serde_json::from_str(&fs::read_to_string(&baseline).expect("baseline not written"))
+            .expect("baseline is not json");
+    assert_eq!(
+        recorded["entries"][0]["function"],
+        "type Shop.Domain.Customer"
+    );
+    assert_eq!(recorded["entries"][0]["loc"], 26);
+    fs::remove_file(baseline).ok();
+}
+
+#[test]
+fn baseline_excuses_complexity_only_up_to_recorded_metrics() {
+    let baseline = temp_baseline("metrics");
+    let run_with = |extra: &[&str]| {
+        Command::new(BIN)
+            .args([
+                "--path",
+                "tests/complexity_proj/lib.rs",
+                "--offending-max-loc-per-file",
+                "1000",
+                "--offending-max-loc-per-fn",
+                "1000",
+                "--offending-max-cyclomatic",
+                "3",
+            ])
+            .args(extra)
+            .output()
+            .expect("failed to run loc-checker")
+    };
+    let check = ["--check", "--baseline", baseline.to_str().unwrap()];
+
+    assert!(
+        run_with(&["--write-baseline", baseline.to_str().unwrap()])
+            .status
+            .success()
+    );
+    let recorded: Value =
+        serde_json::from_str(&fs::read_to_string(&baseline).expect("baseline not written"))
+            .expect("baseline is not json");
+    let classify = recorded["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["function"] == "classify")
+        .expect("classify recorded");
+    assert_eq!(classify["metrics"]["cyclomatic"], 7);
+    assert_eq!(run_with(&check).status.code(), Some(0));
+
+    // A LOC-only entry, or one recorded before the function got more complex, no longer excuses
+    // the complexity offender.
+    for metrics in [
+        "",
+        r#","metrics":{"cyclomatic":6,"cognitive":99,"max_nesting":9}"#,
+    ] {
+        fs::write(
+            &baseline,
+            format!(
+                r#"{{"entries":[{{"path":"lib.rs","function":"classify","loc":17{metrics}}}]}}"#
+            ),
+        )
+        .unwrap();
+        assert_eq!(run_with(&check).status.code(), Some(EXIT_OFFENDERS_FOUND));
+    }
+    fs::remove_file(baseline).ok();
+}
+
+#[test]
+fn violations_skip_metrics_the_baseline_still_excuses() {
+    let baseline = temp_baseline("violations");
+    fs::write(
+        &baseline,
+        r#"{"entries":[{"path":"lib.rs","function":"classify","loc":17,"metrics":{"cyclomatic":6,"cognitive":99,"max_nesting":9}}]}"#,
+    )
+    .unwrap();
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests/complexity_proj/lib.rs",
+            "--output-format",
+            "sarif",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "10",
+            "--offending-max-cyclomatic",
+            "3",
+            "--baseline",
+            baseline.to_str().unwrap(),
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+    fs::remove_file(baseline).ok();
+    assert!(output.status.success(), "{output:?}");
+
+    // classify only offends because its complexity grew; its LOC is still baselined.
+    let sarif: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+    let rule_ids = sarif["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["ruleId"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(rule_ids, ["function-too-complex"]);
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .args(args)
+        .output()
+        .expect("failed to run loc-checker")
+}
+
+fn temp_baseline(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "loc-checker-baseline-{name}-{}.json",
+        std::process::id()
+    ))
+}
@@ -0,0 +1,12 @@
+namespace Billing;
+
+public class Service
+{
+    public int Total(int a, int b)
+    {
+        var sum = a + b;
+        sum += a;
+        sum += b;
+        return sum;
+    }
+}
@@ -0,0 +1,9 @@
+pub trait Report {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str("header");
+        out.push_str("body");
+        out.push_str("footer");
+        out
+    }
+}
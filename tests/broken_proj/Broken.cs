@@ -0,0 +1,7 @@
+public class Broken
+{
+    public int Run()
+    {
+        return 1 +;
+    }
+}
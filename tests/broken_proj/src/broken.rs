@@ -0,0 +1,8 @@
+pub fn half_written(value: i32) -> i32 {
+    let partial = value +;
+    partial
+}
+
+pub fn untouched() -> i32 {
+    7
+}
@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+
+const ORIGINAL_LIB: &str = "\
+pub fn first() -> u32 {
+    1
+}
+
+pub fn second() -> u32 {
+    2
+}
+";
+
+#[test]
+fn changed_since_limits_scan_to_added_and_modified_files() {
+    let repo = GitRepo::new("files");
+    let report = repo.scan(&["--changed-since", "HEAD"]);
+
+    let paths = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["path"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["src/added.rs", "src/lib.rs"]);
+    assert_eq!(report["changed_since"]["ref"], "HEAD");
+    assert_eq!(report["changed_since"]["functions_only"], false);
+}
+
+#[test]
+fn changed_functions_only_keeps_functions_touching_hunks() {
+    let repo = GitRepo::new("functions");
+    let report = repo.scan(&["--changed-since", "HEAD", "--changed-functions-only"]);
+
+    let lib = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == "src/lib.rs")
+        .expect("src/lib.rs should be reported");
+    let names = lib["summary"]["file_scope_functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["second"]);
+}
+
+#[test]
+fn unknown_ref_is_a_scan_error() {
+    let repo = GitRepo::new("bad-ref");
+    let output = Command::new(BIN)
+        .args(["--path", repo.root.to_str().unwrap()])
+        .args(["--changed-since", "does-not-exist"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(stderr.contains("does-not-exist"), "got: {stderr}");
+}
+
+#[test]
+fn untracked_files_count_as_changed_unless_ignored() {
+    let repo = GitRepo::new("untracked");
+    fs::write(repo.root.join("src/fresh.rs"), "pub fn fresh() {}\n").unwrap();
+    fs::write(repo.root.join("src/ignored.rs"), "pub fn ignored() {}\n").unwrap();
+    fs::write(repo.root.join(".gitignore"), "src/ignored.rs\n").unwrap();
+    let report = repo.scan(&["--changed-since", "HEAD", "--changed-functions-only"]);
+
+    let fresh = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == "src/fresh.rs")
+        .expect("untracked src/fresh.rs should be reported");
+    assert_eq!(fresh["summary"]["file_scope_functions"][0]["name"], "fresh");
+    assert!(
+        !report["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|file| file["path"] == "src/ignored.rs")
+    );
+}
+
+#[test]
+fn paths_git_quotes_in_diff_headers_are_still_matched() {
+    let repo = GitRepo::new("odd-paths");
+    let spaced = repo.root.join("odd dir/my file.rs");
+    let quoted = repo.root.join("src/say \"hi\".rs");
+    fs::create_dir_all(spaced.parent().unwrap()).unwrap();
+    fs::write(&spaced, ORIGINAL_LIB).unwrap();
+    fs::write(&quoted, ORIGINAL_LIB).unwrap();
+    git(&repo.root, &["add", "--all"]);
+    git(&repo.root, &["commit", "--quiet", "--message", "odd paths"]);
+    for path in [&spaced, &quoted] {
+        fs::write(path, ORIGINAL_LIB.replace("    2\n", "    3\n")).unwrap();
+    }
+    let report = repo.scan(&["--changed-since", "HEAD", "--changed-functions-only"]);
+
+    let files = report["files"].as_array().unwrap();
+    for path in ["odd dir/my file.rs", "src/say \"hi\".rs"] {
+        let file = files
+            .iter()
+            .find(|file| file["path"] == path)
+            .unwrap_or_else(|| panic!("{path} should be reported: {files:?}"));
+        assert_eq!(file["summary"]["file_scope_functions"][0]["name"], "second");
+    }
+}
+
+#[test]
+fn missing_git_is_reported_clearly() {
+    let repo = GitRepo::new("no-git");
+    let output = Command::new(BIN)
+        .args(["--path", repo.root.to_str().unwrap()])
+        .args(["--changed-since", "HEAD"])
+        .env("PATH", "")
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("git was not found on PATH"),
+        "got: {stderr}"
+    );
+}
+
+/// A throwaway repository with one commit and a modified plus an added file on top.
+struct GitRepo {
+    root: PathBuf,
+}
+
+impl GitRepo {
+    fn new(name: &str) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("loc-checker-changed-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+
+        fs::write(root.join("src/lib.rs"), ORIGINAL_LIB).unwrap();
+        fs::write(root.join("src/untouched.rs"), "pub fn same() {}\n").unwrap();
+        git(&root, &["init", "--quiet"]);
+        git(&root, &["add", "--all"]);
+        git(&root, &["commit", "--quiet", "--message", "initial"]);
+
+        fs::write(
+            root.join("src/lib.rs"),
+            ORIGINAL_LIB.replace("    2\n", "    let value = 2;\n    value\n"),
+        )
+        .unwrap();
+        fs::write(root.join("src/added.rs"), "pub fn added() {}\n").unwrap();
+        git(&root, &["add", "src/added.rs"]);
+
+        Self { root }
+    }
+
+    fn scan(&self, args: &[&str]) -> Value {
+        let output = Command::new(BIN)
+            .args([
+                "--path",
+                self.root.to_str().unwrap(),
+                "--output-format",
+                "json",
+            ])
+            .args(args)
+            .output()
+            .expect("failed to run loc-checker");
+        assert!(output.status.success(), "{output:?}");
+        serde_json::from_slice(&output.stdout).expect("stdout is not json")
+    }
+}
+
+impl Drop for GitRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn git(root: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args([
+            "-c",
+            "user.name=loc-checker",
+            "-c",
+            "user.email=loc-checker@example.invalid",
+            "-c",
+            "commit.gpgsign=false",
+        ])
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {args:?} failed");
+}
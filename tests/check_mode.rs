@@ -0,0 +1,66 @@
+use std::process::{Command, Output};
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/test_proj";
+const EXIT_OFFENDERS_FOUND: i32 = 3;
+
+#[test]
+fn check_mode_exits_with_offender_status_and_summary() {
+    let output = run_check("120", "70");
+
+    assert_eq!(output.status.code(), Some(EXIT_OFFENDERS_FOUND));
+
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert_eq!(
+        stderr.trim(),
+        "check failed: 1 file(s) and 1 function(s) exceed configured limits"
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.contains("build_full_report"),
+        "offender report should still be printed"
+    );
+}
+
+#[test]
+fn check_mode_passes_when_nothing_exceeds_limits() {
+    let output = run_check("1000", "1000");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(stderr.contains("check passed"), "got: {stderr}");
+}
+
+#[test]
+fn scan_errors_use_a_different_exit_status() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests/does_not_exist",
+            "--check",
+            "--offending-max-loc-per-file",
+            "10",
+            "--offending-max-loc-per-fn",
+            "10",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+fn run_check(max_file: &str, max_fn: &str) -> Output {
+    Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--check",
+            "--offending-max-loc-per-file",
+            max_file,
+            "--offending-max-loc-per-fn",
+            max_fn,
+        ])
+        .output()
+        .expect("failed to run loc-checker")
+}
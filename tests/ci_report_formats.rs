@@ -0,0 +1,117 @@
+use std::process::Command;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/complexity_proj";
+const LIMITS: [&str; 6] = [
+    "--offending-max-loc-per-file",
+    "30",
+    "--offending-max-loc-per-fn",
+    "16",
+    "--offending-max-nesting",
+    "2",
+];
+
+#[test]
+fn junit_reports_each_file_as_a_suite() {
+    let output = run(TEST_PATH, "junit", &LIMITS);
+
+    assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(
+        output.contains(r#"<testsuites name="loc-checker" tests="8" failures="2">"#),
+        "got: {output}"
+    );
+    assert!(
+        output.contains(
+            r#"<testsuite name="tests/complexity_proj/Rules.cs" tests="4" failures="1">"#
+        )
+    );
+    assert!(
+        output.contains(r#"<testcase classname="tests/complexity_proj/lib.rs" name="straight"/>"#)
+    );
+    assert!(
+        output.contains(
+            r#"<failure type="function-too-long,function-too-deeply-nested" message="Function `classify` has 17 LOC, above the limit of 16 (rule `default`); Function `classify` has nesting depth 3, above the limit of 2">"#
+        ),
+        "got: {output}"
+    );
+    assert!(output.contains(
+        r#"<failure type="file-too-long" message="File has 35 LOC, above the limit of 30 (rule `default`)">"#
+    ));
+}
+
+#[test]
+fn junit_adds_cases_for_offending_types() {
+    let output = run(
+        "tests/csharp_partial_proj",
+        "junit",
+        &[
+            "--offending-max-loc-per-file",
+            "100",
+            "--offending-max-loc-per-fn",
+            "100",
+            "--offending-max-loc-per-type",
+            "20",
+        ],
+    );
+
+    assert!(
+        output.contains(
+            "<testcase classname=\"tests/csharp_partial_proj/Customer.Api.cs\" name=\"Shop.Domain.Customer\">\n\
+             \x20     <failure type=\"type-too-long\""
+        ),
+        "got: {output}"
+    );
+    assert!(
+        output.contains(r#"tests="14" failures="1""#),
+        "got: {output}"
+    );
+}
+
+#[test]
+fn checkstyle_lists_every_file_with_its_errors() {
+    let output = run(TEST_PATH, "checkstyle", &LIMITS);
+
+    assert_eq!(
+        output,
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<checkstyle version=\"4.3\">\n",
+            "  <file name=\"tests/complexity_proj/Rules.cs\">\n",
+            "    <error line=\"1\" column=\"1\" severity=\"warning\" message=\"File has 35 LOC, above the limit of 30 (rule `default`)\" source=\"loc-checker.file-too-long\"/>\n",
+            "  </file>\n",
+            "  <file name=\"tests/complexity_proj/lib.rs\">\n",
+            "    <error line=\"5\" column=\"1\" severity=\"warning\" message=\"Function `classify` has 17 LOC, above the limit of 16 (rule `default`)\" source=\"loc-checker.function-too-long\"/>\n",
+            "    <error line=\"5\" column=\"1\" severity=\"warning\" message=\"Function `classify` has nesting depth 3, above the limit of 2\" source=\"loc-checker.function-too-deeply-nested\"/>\n",
+            "  </file>\n",
+            "</checkstyle>\n",
+        )
+    );
+}
+
+#[test]
+fn checkstyle_keeps_clean_files() {
+    let output = run(
+        TEST_PATH,
+        "checkstyle",
+        &[
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+        ],
+    );
+
+    assert!(output.contains("  <file name=\"tests/complexity_proj/Rules.cs\"/>\n"));
+    assert!(output.contains("  <file name=\"tests/complexity_proj/lib.rs\"/>\n"));
+    assert!(!output.contains("<error"));
+}
+
+fn run(path: &str, format: &str, extra: &[&str]) -> String {
+    let output = Command::new(BIN)
+        .args(["--path", path, "--output-format", format])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("stdout is not utf-8")
+}
@@ -0,0 +1,15 @@
+// File header comment.
+namespace Comments;
+
+/// <summary>Calculator.</summary>
+public class Calculator
+{
+    /* block comment
+       spanning lines */
+    public int Add(int left, int right)
+    {
+        // explain the sum
+
+        return left + right; // trailing comment
+    }
+}
@@ -0,0 +1,16 @@
+//! Crate-level docs describing the module.
+
+/// Adds two numbers.
+/// Second doc line.
+pub fn add(left: i32, right: i32) -> i32 {
+    // explain the sum
+
+    /* block comment
+       spanning lines */
+    left + right // trailing comment
+}
+
+pub fn banner() -> &'static str {
+    "multi
+line"
+}
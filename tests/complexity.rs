@@ -0,0 +1,230 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/complexity_proj";
+
+#[test]
+fn rust_functions_report_complexity() {
+    let report = run_json(&[]);
+    let summary = file_summary(&report, "lib.rs");
+
+    assert_eq!(
+        metrics(&summary["file_scope_functions"], "name"),
+        vec![
+            ("classify".to_string(), 7, 9, 3),
+            ("straight".to_string(), 1, 0, 0),
+        ]
+    );
+    assert_eq!(
+        metrics(&summary["impl_methods"], "method_name"),
+        vec![("parse".to_string(), 3, 2, 1)]
+    );
+}
+
+#[test]
+fn csharp_members_report_complexity() {
+    let report = run_json(&[]);
+    let summary = file_summary(&report, "Rules.cs");
+
+    assert_eq!(
+        metrics(&summary["impl_methods"], "method_name"),
+        vec![
+            ("Discount".to_string(), 6, 6, 2),
+            ("Flat".to_string(), 1, 0, 0),
+            // The `try` body adds a level of nesting but no cognitive weight.
+            ("Load".to_string(), 3, 2, 2),
+        ]
+    );
+}
+
+#[test]
+fn complexity_limits_flag_short_functions() {
+    let report = run_json(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "1000",
+        "--offending-max-loc-per-fn",
+        "1000",
+        "--offending-max-cyclomatic",
+        "6",
+    ]);
+
+    assert_eq!(report["offender_limits"]["max_cyclomatic"], 6);
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "only classify exceeds the limit: {files:?}");
+    let summary = &files[0]["summary"];
+    assert_eq!(
+        metrics(&summary["file_scope_functions"], "name"),
+        vec![("classify".to_string(), 7, 9, 3)]
+    );
+    assert!(summary["impl_methods"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn check_fails_on_cognitive_limit() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--check",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+            "--offending-max-cognitive",
+            "5",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("check failed: 0 file(s) and 2 function(s) exceed configured limits"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn nesting_limit_flags_deep_functions() {
+    let report = run_json(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "1000",
+        "--offending-max-loc-per-fn",
+        "1000",
+        "--offending-max-nesting",
+        "2",
+    ]);
+
+    assert_eq!(report["offender_limits"]["max_nesting"], 2);
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(
+        files.len(),
+        1,
+        "only classify nests deeper than 2: {files:?}"
+    );
+    assert_eq!(files[0]["path"], "lib.rs");
+    assert_eq!(
+        metrics(&files[0]["summary"]["file_scope_functions"], "name"),
+        vec![("classify".to_string(), 7, 9, 3)]
+    );
+}
+
+#[test]
+fn json_entries_name_the_metric_and_rule_they_exceed() {
+    let report = run_json(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "1000",
+        "--offending-max-loc-per-fn",
+        "100",
+        "--offending-max-nesting",
+        "2",
+        "--offending-max-loc-per-type",
+        "30",
+    ]);
+
+    let lib = file_summary(&report, "lib.rs");
+    let classify = lib["file_scope_functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["name"] == "classify")
+        .expect("classify should offend");
+    assert_eq!(
+        classify["offense"],
+        serde_json::json!([{
+            "metric": "nesting",
+            "actual": 3,
+            "limit": 2,
+            "rule": "default",
+            "rule_id": "function-too-deeply-nested",
+        }]),
+        "only nesting is over its limit"
+    );
+
+    let rules = file_summary(&report, "Rules.cs");
+    assert_eq!(
+        rules["structs"][0]["offense"],
+        serde_json::json!([{
+            "metric": "loc",
+            "actual": 34,
+            "limit": 30,
+            "rule": "default",
+            "rule_id": "type-too-long",
+        }])
+    );
+}
+
+#[test]
+fn tree_shows_which_metric_was_exceeded() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--offenders-only",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "100",
+            "--offending-max-cyclomatic",
+            "3",
+            "--offending-max-nesting",
+            "2",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+
+    assert!(
+        stdout.contains(
+            "classify (17 loc, cyclomatic 7 > 3, nesting 3 > 2) at tests/complexity_proj/lib.rs:5"
+        ),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains(
+        "Billing.Rules::Discount (15 loc, cyclomatic 6 > 3) at tests/complexity_proj/Rules.cs:5"
+    ));
+}
+
+fn run_json(extra: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn file_summary(report: &Value, path: &str) -> Value {
+    report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == path)
+        .unwrap_or_else(|| panic!("{path} missing from report"))["summary"]
+        .clone()
+}
+
+fn metrics(entries: &Value, name_key: &str) -> Vec<(String, u64, u64, u64)> {
+    let mut metrics = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry[name_key].as_str().unwrap().to_string(),
+                entry["cyclomatic"].as_u64().unwrap(),
+                entry["cognitive"].as_u64().unwrap(),
+                entry["max_nesting"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    metrics.sort();
+    metrics
+}
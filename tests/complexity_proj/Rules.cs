@@ -0,0 +1,41 @@
+namespace Billing;
+
+public class Rules
+{
+    public int Discount(int total, bool member)
+    {
+        if (member && total > 100)
+        {
+            return 10;
+        }
+
+        foreach (var step in new[] { 50, 20 })
+        {
+            if (total > step)
+            {
+                return step / 10;
+            }
+        }
+
+        return total > 0 ? 1 : 0;
+    }
+
+    public int Flat() => 5;
+
+    public int Load(string text)
+    {
+        try
+        {
+            if (text.Length > 0)
+            {
+                return int.Parse(text);
+            }
+        }
+        catch (FormatException)
+        {
+            return -1;
+        }
+
+        return 0;
+    }
+}
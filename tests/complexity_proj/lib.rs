@@ -0,0 +1,33 @@
+pub fn straight(a: u32) -> u32 {
+    a + 1
+}
+
+pub fn classify(values: &[i32], strict: bool) -> &'static str {
+    let mut label = "empty";
+    for value in values {
+        if *value < 0 && strict {
+            return "negative";
+        } else if *value == 0 {
+            label = "zero";
+        } else {
+            label = match value {
+                1 => "one",
+                2 | 3 => "few",
+                _ => "many",
+            };
+        }
+    }
+    label
+}
+
+pub struct Parser;
+
+impl Parser {
+    pub fn parse(&self, input: &str) -> Option<u32> {
+        let digits = input.trim();
+        if digits.is_empty() || digits.len() > 9 {
+            return None;
+        }
+        digits.parse().ok()
+    }
+}
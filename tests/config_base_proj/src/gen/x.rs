@@ -0,0 +1,3 @@
+pub fn generated() -> u32 {
+    1
+}
@@ -0,0 +1,3 @@
+pub fn keep() -> u32 {
+    1
+}
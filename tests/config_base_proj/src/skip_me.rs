@@ -0,0 +1,3 @@
+pub fn skipped() -> u32 {
+    1
+}
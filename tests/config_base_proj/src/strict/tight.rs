@@ -0,0 +1,3 @@
+pub fn tight() -> u32 {
+    1
+}
@@ -0,0 +1,192 @@
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/config_proj";
+
+#[test]
+fn discovers_config_file_and_echoes_effective_settings() {
+    let json = run_json(&["--path", TEST_PATH]);
+
+    let config_file = json
+        .get("config_file")
+        .and_then(Value::as_str)
+        .expect("missing config_file");
+    assert!(config_file.ends_with("config_proj/loc-checker.toml"));
+    assert_eq!(
+        json.get("exclude_path_regexes").and_then(Value::as_array),
+        Some(&vec![Value::from(r"generated\.rs$")])
+    );
+    assert_eq!(paths(&json), ["src/lib.rs", "src/nested/deep.rs"]);
+}
+
+#[test]
+fn config_file_is_discovered_from_nested_paths() {
+    let json = run_json(&["--path", "tests/config_proj/src/nested"]);
+
+    assert!(
+        json.get("config_file")
+            .and_then(Value::as_str)
+            .is_some_and(|path| path.ends_with("config_proj/loc-checker.toml"))
+    );
+}
+
+#[test]
+fn config_file_patterns_stay_relative_to_its_directory() {
+    let from_root = run_json(&["--path", "tests/config_base_proj"]);
+    assert_eq!(paths(&from_root), ["src/keep.rs", "src/strict/tight.rs"]);
+
+    // Scanning below the config file must exclude and match the same files.
+    let from_src = run_json(&["--path", "tests/config_base_proj/src", "--offenders-only"]);
+    assert_eq!(paths(&from_src), ["strict/tight.rs"]);
+    let offense = &from_src["files"][0]["offense"]["function"];
+    assert_eq!(offense["rule"], "src/strict/**");
+    assert_eq!(offense["max_loc"], 1);
+    assert_eq!(
+        from_src.get("test_attributes").and_then(Value::as_array),
+        Some(&vec![Value::from("rstest")])
+    );
+}
+
+#[test]
+fn cli_flags_take_precedence_over_config_file() {
+    let json = run_json(&["--path", TEST_PATH, "--exclude-path", "deep"]);
+
+    assert_eq!(
+        json.get("exclude_path_regexes").and_then(Value::as_array),
+        Some(&vec![Value::from("deep")])
+    );
+    assert_eq!(paths(&json), ["src/generated.rs", "src/lib.rs"]);
+}
+
+#[test]
+fn offender_thresholds_can_come_from_config_file() {
+    let json = run_json(&["--path", TEST_PATH, "--offenders-only"]);
+
+    let limits = json
+        .get("offender_limits")
+        .expect("missing offender_limits");
+    assert_eq!(
+        limits.get("max_loc_per_fn").and_then(Value::as_u64),
+        Some(3)
+    );
+    assert_eq!(
+        limits.get("max_loc_per_file").and_then(Value::as_u64),
+        Some(100)
+    );
+    assert_eq!(paths(&json), ["src/lib.rs"]);
+
+    let overridden = run_json(&[
+        "--path",
+        TEST_PATH,
+        "--offenders-only",
+        "--offending-max-loc-per-fn",
+        "10",
+    ]);
+    assert!(paths(&overridden).is_empty());
+}
+
+#[test]
+fn explicit_config_flag_replaces_discovery() {
+    let json = run_json(&[
+        "--path",
+        TEST_PATH,
+        "--config",
+        "tests/config_proj/strict.toml",
+    ]);
+
+    assert!(
+        json.get("config_file")
+            .and_then(Value::as_str)
+            .is_some_and(|path| path.ends_with("strict.toml"))
+    );
+    assert_eq!(paths(&json), ["src/generated.rs", "src/lib.rs"]);
+}
+
+#[test]
+fn offenders_only_without_thresholds_is_rejected() {
+    let output = run(&[
+        "--path",
+        "tests/test_proj",
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "10",
+    ]);
+
+    assert!(!output.status.success(), "missing thresholds should fail");
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("--offending-max-loc-per-fn"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn cli_flags_switch_off_booleans_enabled_in_config_file() {
+    let config = [
+        "--path",
+        TEST_PATH,
+        "--config",
+        "tests/config_proj/offenders.toml",
+    ];
+    let json = run_json(&config);
+    assert_eq!(json["offender_limits"]["max_loc_per_fn"], 3);
+    assert_eq!(paths(&json), ["src/lib.rs"]);
+
+    let mut full = config.to_vec();
+    full.push("--no-offenders-only");
+    let json = run_json(&full);
+    assert!(json["offender_limits"].is_null());
+    assert_eq!(
+        paths(&json),
+        ["src/generated.rs", "src/lib.rs", "src/nested/deep.rs"]
+    );
+}
+
+#[test]
+fn thresholds_without_an_offender_mode_are_rejected() {
+    let output = run(&[
+        "--path",
+        "tests/test_proj",
+        "--offending-max-loc-per-fn",
+        "10",
+        "--offending-max-nesting",
+        "3",
+    ]);
+
+    assert!(!output.status.success(), "unused thresholds should fail");
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains(
+            "offender thresholds (--offending-max-loc-per-fn, --offending-max-nesting) require \
+             an offender mode"
+        ),
+        "got: {stderr}"
+    );
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(BIN)
+        .args(args)
+        .args(["--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker")
+}
+
+fn run_json(args: &[&str]) -> Value {
+    let output = run(args);
+    assert!(output.status.success(), "binary exited with failure");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    serde_json::from_str(&stdout).expect("invalid json output")
+}
+
+fn paths(json: &Value) -> Vec<&str> {
+    json.get("files")
+        .and_then(Value::as_array)
+        .expect("missing files array")
+        .iter()
+        .filter_map(|entry| entry.get("path").and_then(Value::as_str))
+        .collect()
+}
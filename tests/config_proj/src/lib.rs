@@ -0,0 +1,11 @@
+pub mod generated;
+
+pub fn short() -> i32 {
+    1
+}
+
+pub fn longer(value: i32) -> i32 {
+    let doubled = value * 2;
+    let tripled = value * 3;
+    doubled + tripled
+}
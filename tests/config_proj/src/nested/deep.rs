@@ -0,0 +1,3 @@
+pub fn deep() -> i32 {
+    2
+}
@@ -0,0 +1,114 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/comments_proj";
+
+#[test]
+fn reports_code_comment_and_blank_lines_separately() {
+    let json = run_json("code+comments");
+
+    assert_eq!(
+        json.get("count_mode").and_then(Value::as_str),
+        Some("code+comments")
+    );
+
+    let rust = find_file(&json, "commented.rs");
+    let summary = rust.get("summary").expect("missing summary");
+    assert_eq!(line_counts(summary), (7, 6, 3));
+    assert_eq!(summary.get("total_loc").and_then(Value::as_u64), Some(13));
+
+    let add = find_named(summary, "file_scope_functions", "add");
+    assert_eq!(
+        line_counts(add),
+        (3, 5, 1),
+        "doc comments belong to the function"
+    );
+
+    let csharp = find_file(&json, "Commented.cs");
+    let summary = csharp.get("summary").expect("missing summary");
+    assert_eq!(line_counts(summary), (8, 5, 2));
+}
+
+#[test]
+fn count_mode_selects_loc_used_for_rankings() {
+    let code = run_json("code");
+    let code_summary = find_file(&code, "commented.rs")
+        .get("summary")
+        .expect("missing summary");
+    assert_eq!(
+        code_summary.get("total_loc").and_then(Value::as_u64),
+        Some(7)
+    );
+    assert_eq!(top_function_names(code_summary), ["banner", "add"]);
+
+    let physical = run_json("physical");
+    let physical_summary = find_file(&physical, "commented.rs")
+        .get("summary")
+        .expect("missing summary");
+    assert_eq!(
+        physical_summary.get("total_loc").and_then(Value::as_u64),
+        Some(16)
+    );
+    assert_eq!(top_function_names(physical_summary), ["add", "banner"]);
+}
+
+fn run_json(count_mode: &str) -> Value {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--output-format",
+            "json",
+            "--count-mode",
+            count_mode,
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(output.status.success(), "binary exited with failure");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    serde_json::from_str(&stdout).expect("invalid json output")
+}
+
+fn find_file<'a>(json: &'a Value, path: &str) -> &'a Value {
+    json.get("files")
+        .and_then(Value::as_array)
+        .expect("missing files array")
+        .iter()
+        .find(|entry| entry.get("path").and_then(Value::as_str) == Some(path))
+        .expect("missing file entry")
+}
+
+fn find_named<'a>(summary: &'a Value, section: &str, name: &str) -> &'a Value {
+    summary
+        .get(section)
+        .and_then(Value::as_array)
+        .expect("missing section")
+        .iter()
+        .find(|entry| entry.get("name").and_then(Value::as_str) == Some(name))
+        .expect("missing named entry")
+}
+
+fn line_counts(entry: &Value) -> (u64, u64, u64) {
+    let lines = entry.get("lines").expect("missing lines breakdown");
+    let count = |key: &str| {
+        lines
+            .get(key)
+            .and_then(Value::as_u64)
+            .expect("missing count")
+    };
+    (count("code"), count("comment"), count("blank"))
+}
+
+fn top_function_names(summary: &Value) -> Vec<&str> {
+    summary
+        .get("top_functions")
+        .and_then(Value::as_array)
+        .expect("missing top_functions")
+        .iter()
+        .filter_map(|entry| entry.get("name").and_then(Value::as_str))
+        .collect()
+}
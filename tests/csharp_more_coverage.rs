@@ -5,21 +5,60 @@ use serde_json::Value;
 const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
 
 #[test]
-fn mixed_language_repo_requires_explicit_lang_when_auto_detect_is_ambiguous() {
-    let output = Command::new(BIN)
-        .args(["--path", "tests/mixed_proj", "--output-format", "json"])
-        .output()
-        .expect("failed to run loc-checker");
+fn mixed_language_repo_auto_detect_scans_every_language() {
+    let json = run_json("tests/mixed_proj");
+
+    assert_eq!(
+        json.get("language").and_then(Value::as_str),
+        Some("rust,csharp")
+    );
 
-    assert!(!output.status.success(), "auto-detect should fail for mixed repo");
+    let files = files(&json);
+    assert_eq!(files.len(), 2);
+    assert_eq!(
+        find_file(files, "src/lib.rs").get("language").and_then(Value::as_str),
+        Some("rust")
+    );
+    assert_eq!(
+        find_file(files, "Program.cs").get("language").and_then(Value::as_str),
+        Some("csharp")
+    );
 
-    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    let by_language = json
+        .get("totals")
+        .and_then(|totals| totals.get("by_language"))
+        .and_then(Value::as_array)
+        .expect("missing per-language totals");
+    assert_eq!(by_language.len(), 2);
     assert!(
-        stderr.contains("unable to auto-detect language"),
-        "expected ambiguity error, got: {stderr}"
+        by_language.iter().all(|entry| entry.get("files").and_then(Value::as_u64) == Some(1)),
+        "expected one file per language"
     );
 }
 
+#[test]
+fn mixed_language_repo_honors_explicit_lang_list() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests/mixed_proj",
+            "--output-format",
+            "json",
+            "--lang",
+            "csharp",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "binary exited with failure");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    let json: Value = serde_json::from_str(&stdout).expect("invalid json output");
+    assert_eq!(json.get("language").and_then(Value::as_str), Some("csharp"));
+    let files = files(&json);
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].get("path").and_then(Value::as_str), Some("Program.cs"));
+}
+
 #[test]
 fn csharp_shape_fixture_reports_advanced_members() {
     let json = run_json("tests/csharp_shapes");
@@ -37,10 +76,10 @@ fn csharp_shape_fixture_reports_advanced_members() {
         .and_then(Value::as_array)
         .expect("missing structs");
     assert!(
-        has_named_entry(structs, "Accumulator")
-            && has_named_entry(structs, "PersonRecord")
-            && has_named_entry(structs, "Widget")
-            && has_named_entry(structs, "Notifier"),
+        has_named_entry(structs, "Shapes.Accumulator")
+            && has_named_entry(structs, "Shapes.PersonRecord")
+            && has_named_entry(structs, "Shapes.Widget")
+            && has_named_entry(structs, "Shapes.Notifier"),
         "expected record, struct, and class declarations"
     );
 
@@ -63,7 +102,7 @@ fn csharp_shape_fixture_reports_advanced_members() {
         .expect("missing trait_methods");
     assert!(
         trait_methods.iter().any(|entry| {
-            entry.get("trait_name").and_then(Value::as_str) == Some("IWorker")
+            entry.get("trait_name").and_then(Value::as_str) == Some("Shapes.IWorker")
                 && entry.get("method_name").and_then(Value::as_str) == Some("Execute")
         }),
         "expected default interface method to be captured"
@@ -74,7 +113,7 @@ fn csharp_shape_fixture_reports_advanced_members() {
         .and_then(Value::as_array)
         .expect("missing delegates");
     assert!(
-        has_named_entry(delegates, "Processor"),
+        has_named_entry(delegates, "Shapes.Processor"),
         "expected delegate declaration to be captured"
     );
 
@@ -83,7 +122,8 @@ fn csharp_shape_fixture_reports_advanced_members() {
         .and_then(Value::as_array)
         .expect("missing events");
     assert!(
-        has_named_entry(events, "Changed") && has_named_entry(events, "Updated"),
+        has_named_entry(events, "Shapes.Notifier.Changed")
+            && has_named_entry(events, "Shapes.Notifier.Updated"),
         "expected event declarations to be captured"
     );
 }
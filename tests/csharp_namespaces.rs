@@ -0,0 +1,122 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/csharp_namespaces_proj";
+
+#[test]
+fn nested_types_are_qualified_with_namespace_and_outer_types() {
+    let report = run_json();
+    let summary = file_summary(&report, "Orders.cs");
+
+    assert_eq!(
+        names(&summary["top_functions"]),
+        vec![
+            "Company.App.Outer.Inner.Run",
+            "Company.App.Billing.Invoice.Total",
+            "Company.App.Outer.Count",
+        ]
+    );
+    assert_eq!(
+        names(&summary["structs"]),
+        vec![
+            "Company.App.Outer",
+            "Company.App.Outer.Inner",
+            "Company.App.Billing.Invoice",
+        ]
+    );
+
+    let targets = summary["impl_methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["impl_target"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        targets,
+        vec![
+            "Company.App.Outer.Inner",
+            "Company.App.Billing.Invoice",
+            "Company.App.Outer",
+        ]
+    );
+}
+
+#[test]
+fn same_named_types_in_different_namespaces_stay_distinct() {
+    let report = run_json();
+
+    assert_eq!(
+        names(&file_summary(&report, "Legacy.cs")["structs"]),
+        vec!["Company.Legacy.Invoice"]
+    );
+    assert!(
+        names(&file_summary(&report, "Orders.cs")["structs"])
+            .contains(&"Company.App.Billing.Invoice".to_string())
+    );
+}
+
+#[test]
+fn namespace_totals_roll_up_across_files() {
+    let report = run_json();
+
+    let totals = report["totals"]["by_namespace"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["namespace"].as_str().unwrap().to_string(),
+                entry["files"].as_u64().unwrap(),
+                entry["total_loc"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        totals,
+        vec![
+            ("Company.App".to_string(), 1, 25),
+            ("Company.App.Billing".to_string(), 1, 10),
+            ("Company.Legacy".to_string(), 1, 9),
+        ]
+    );
+
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .output()
+        .expect("failed to run loc-checker");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(stdout.contains("totals by namespace\n├── Company.App: 1 files, 25 loc"));
+    assert!(stdout.contains("└── Company.Legacy: 1 files, 9 loc"));
+}
+
+fn run_json() -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn file_summary<'a>(report: &'a Value, path: &str) -> &'a Value {
+    report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == path)
+        .map_or_else(
+            || panic!("{path} missing from report"),
+            |file| &file["summary"],
+        )
+}
+
+fn names(entries: &Value) -> Vec<String> {
+    entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect()
+}
@@ -0,0 +1,10 @@
+namespace Company.Legacy;
+
+public class Invoice
+{
+    public decimal Total()
+    {
+        var amount = 0m;
+        return amount;
+    }
+}
@@ -0,0 +1,27 @@
+namespace Company.App
+{
+    public class Outer
+    {
+        public class Inner
+        {
+            public void Run()
+            {
+                var total = 0;
+                total += 1;
+            }
+        }
+
+        public int Count() => 1;
+    }
+
+    namespace Billing
+    {
+        public class Invoice
+        {
+            public decimal Total()
+            {
+                return 0m;
+            }
+        }
+    }
+}
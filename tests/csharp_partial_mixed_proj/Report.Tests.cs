@@ -0,0 +1,10 @@
+using Xunit;
+
+public partial class Report
+{
+    [Fact]
+    public void SizeIsOne()
+    {
+        Assert.Equal(1, Size());
+    }
+}
@@ -0,0 +1,7 @@
+public partial class Report
+{
+    public int Size()
+    {
+        return 1;
+    }
+}
@@ -0,0 +1,7 @@
+pub struct Report;
+
+impl Report {
+    pub fn render(&self) -> u32 {
+        1
+    }
+}
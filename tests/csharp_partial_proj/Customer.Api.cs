@@ -0,0 +1,14 @@
+namespace Shop.Domain;
+
+public partial class Customer
+{
+    public string ToJson()
+    {
+        return "{\"name\":\"" + Name + "\"}";
+    }
+}
+
+public class Order
+{
+    public int Id { get; set; }
+}
@@ -0,0 +1,13 @@
+namespace Shop.Domain;
+
+public partial class Customer
+{
+    public bool IsValid()
+    {
+        if (Name.Length == 0)
+        {
+            return false;
+        }
+        return Name.Length < 100;
+    }
+}
@@ -0,0 +1,11 @@
+namespace Shop.Domain;
+
+public partial class Customer
+{
+    public string Name { get; set; } = "";
+
+    public void Rename(string name)
+    {
+        Name = name;
+    }
+}
@@ -0,0 +1,6 @@
+namespace Shop.Domain;
+
+public partial class Invoice
+{
+    public decimal Total { get; set; }
+}
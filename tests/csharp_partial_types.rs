@@ -0,0 +1,174 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/csharp_partial_proj";
+
+#[test]
+fn partial_declarations_merge_across_files() {
+    let report = run_json(&[]);
+
+    let types = report["partial_types"].as_array().unwrap();
+    assert_eq!(types.len(), 1, "only Customer is partial: {types:?}");
+    let customer = &types[0];
+    assert_eq!(customer["name"], "Shop.Domain.Customer");
+    assert_eq!(customer["total_loc"], 26);
+
+    let parts = customer["parts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|part| {
+            (
+                part["path"].as_str().unwrap().to_string(),
+                part["loc"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        parts,
+        vec![
+            ("Customer.Api.cs".to_string(), 7),
+            ("Customer.Validation.cs".to_string(), 11),
+            ("Customer.cs".to_string(), 8),
+        ]
+    );
+
+    let members = customer["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        members,
+        vec!["IsValid", "Rename", "ToJson", "Name.get", "Name.set"]
+    );
+}
+
+#[test]
+fn per_type_limit_flags_merged_size_below_file_limit() {
+    let report = run_json(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "100",
+        "--offending-max-loc-per-fn",
+        "100",
+        "--offending-max-loc-per-type",
+        "20",
+    ]);
+
+    assert!(report["files"].as_array().unwrap().is_empty());
+    assert_eq!(report["offender_limits"]["max_loc_per_type"], 20);
+    let types = report["partial_types"].as_array().unwrap();
+    assert_eq!(types.len(), 1);
+    assert_eq!(types[0]["name"], "Shop.Domain.Customer");
+}
+
+#[test]
+fn offender_modes_drop_types_without_a_type_limit() {
+    let report = run_json(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "100",
+        "--offending-max-loc-per-fn",
+        "100",
+    ]);
+
+    assert!(report["partial_types"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn single_partial_declaration_is_checked_as_a_plain_type() {
+    let report = run_json(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "100",
+        "--offending-max-loc-per-fn",
+        "100",
+        "--offending-max-loc-per-type",
+        "3",
+    ]);
+
+    // Invoice is declared `partial` in Invoice.cs only, so there is nothing to merge.
+    let types = report["partial_types"].as_array().unwrap();
+    assert_eq!(types.len(), 1, "{types:?}");
+    assert_eq!(types[0]["name"], "Shop.Domain.Customer");
+    let invoice = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == "Invoice.cs")
+        .expect("Invoice.cs is reported");
+    assert_eq!(
+        invoice["summary"]["structs"][0]["name"],
+        "Shop.Domain.Invoice"
+    );
+}
+
+#[test]
+fn members_include_tests_but_not_rust_methods_of_the_same_name() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests/csharp_partial_mixed_proj",
+            "--output-format",
+            "json",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+
+    let types = report["partial_types"].as_array().unwrap();
+    assert_eq!(types.len(), 1, "{types:?}");
+    let members = types[0]["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    // report.rs declares `impl Report { fn render }`, which is not part of the C# type.
+    assert_eq!(members, vec!["SizeIsOne", "Size"]);
+}
+
+#[test]
+fn check_fails_on_oversized_partial_type() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--check",
+            "--offending-max-loc-per-file",
+            "100",
+            "--offending-max-loc-per-fn",
+            "100",
+            "--offending-max-loc-per-type",
+            "20",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains(
+            "check failed: 0 file(s), 0 function(s) and 1 type(s) exceed configured limits"
+        ),
+        "got: {stderr}"
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(stdout.contains("partial types\n└── Shop.Domain.Customer (26 loc in 3 files;"));
+}
+
+fn run_json(extra: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
@@ -0,0 +1,88 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/csharp_tests_proj";
+
+#[test]
+fn xunit_nunit_and_mstest_methods_are_tests() {
+    let summary = file_summary("CalculatorTests.cs");
+    let tests = names(&summary["test_functions"]);
+
+    for expected in [
+        "TestsApp.Tests.XunitCalculatorTests.AddsNumbers",
+        "TestsApp.Tests.XunitCalculatorTests.AddsTheory",
+        "TestsApp.Tests.XunitCalculatorTests.QualifiedAttribute",
+        "TestsApp.Tests.NunitCalculatorTests.AddsWithNunit",
+        "TestsApp.Tests.NunitCalculatorTests.AddsCases",
+        "TestsApp.Tests.MstestCalculatorTests.AddsWithMstest",
+        "TestsApp.Tests.MstestCalculatorTests.AddsRows",
+    ] {
+        assert!(tests.contains(&expected.to_string()), "missing {expected}");
+    }
+}
+
+#[test]
+fn test_fixture_classes_route_every_member_to_tests() {
+    let summary = file_summary("CalculatorTests.cs");
+
+    assert!(
+        names(&summary["test_functions"])
+            .contains(&"TestsApp.Tests.NunitCalculatorTests.CreateSubject".to_string())
+    );
+    assert!(names(&summary["test_functions"]).contains(&"local Expected".to_string()));
+
+    // xUnit has no class-level attribute, so undecorated helpers stay production methods.
+    let impl_methods = summary["impl_methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}::{}",
+                entry["impl_target"].as_str().unwrap(),
+                entry["method_name"].as_str().unwrap()
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        impl_methods,
+        vec!["TestsApp.Tests.XunitCalculatorTests::CreateSubject"]
+    );
+}
+
+#[test]
+fn production_code_has_no_tests() {
+    let summary = file_summary("Calculator.cs");
+
+    assert!(summary["test_functions"].as_array().unwrap().is_empty());
+    assert_eq!(summary["impl_methods"].as_array().unwrap().len(), 1);
+}
+
+fn file_summary(path: &str) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+    report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == path)
+        .map_or_else(
+            || panic!("{path} missing from report"),
+            |file| file["summary"].clone(),
+        )
+}
+
+fn names(entries: &Value) -> Vec<String> {
+    entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect()
+}
@@ -0,0 +1,9 @@
+namespace TestsApp;
+
+public class Calculator
+{
+    public int Add(int left, int right)
+    {
+        return left + right;
+    }
+}
@@ -0,0 +1,70 @@
+using Microsoft.VisualStudio.TestTools.UnitTesting;
+using NUnit.Framework;
+using Xunit;
+
+namespace TestsApp.Tests;
+
+public class XunitCalculatorTests
+{
+    [Fact]
+    public void AddsNumbers()
+    {
+        Assert.Equal(3, new Calculator().Add(1, 2));
+    }
+
+    [Theory]
+    [InlineData(1, 2)]
+    public void AddsTheory(int left, int right)
+    {
+        int Expected() => left + right;
+        Assert.Equal(Expected(), new Calculator().Add(left, right));
+    }
+
+    [Xunit.FactAttribute]
+    public void QualifiedAttribute()
+    {
+        Assert.True(true);
+    }
+
+    private Calculator CreateSubject()
+    {
+        return new Calculator();
+    }
+}
+
+[TestFixture]
+public class NunitCalculatorTests
+{
+    private Calculator CreateSubject()
+    {
+        return new Calculator();
+    }
+
+    [Test]
+    public void AddsWithNunit()
+    {
+        Assert.AreEqual(3, CreateSubject().Add(1, 2));
+    }
+
+    [TestCase(2, 2)]
+    public void AddsCases(int left, int right)
+    {
+        Assert.AreEqual(4, CreateSubject().Add(left, right));
+    }
+}
+
+[TestClass]
+public class MstestCalculatorTests
+{
+    [TestMethod]
+    public void AddsWithMstest()
+    {
+        Assert.AreEqual(3, new Calculator().Add(1, 2));
+    }
+
+    [DataTestMethod]
+    public void AddsRows()
+    {
+        Assert.AreEqual(5, new Calculator().Add(2, 3));
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const OLD_PATH: &str = "tests/diff_proj/old";
+const NEW_PATH: &str = "tests/diff_proj/new";
+
+#[test]
+fn diff_of_scanned_paths_reports_file_directory_and_function_changes() {
+    let report = diff_json(OLD_PATH, NEW_PATH);
+
+    assert_eq!(report["old"]["total_loc"], 11);
+    assert_eq!(report["new"]["total_loc"], 18);
+    assert_eq!(report["total_loc_delta"], 7);
+
+    let files = report["files"].as_array().expect("files array");
+    assert_eq!(
+        files
+            .iter()
+            .map(|file| (
+                file["path"].as_str().unwrap(),
+                file["status"].as_str().unwrap()
+            ))
+            .collect::<Vec<_>>(),
+        vec![("src/lib.rs", "modified"), ("src/parser/lexer.rs", "added")]
+    );
+
+    let directories = report["directories"].as_array().expect("directories array");
+    let parser = directories
+        .iter()
+        .find(|entry| entry["path"] == "src/parser")
+        .expect("src/parser rollup");
+    assert_eq!(parser["old_files"], 1);
+    assert_eq!(parser["new_files"], 2);
+    assert_eq!(parser["delta"], 6);
+
+    let functions = report["functions"].as_array().expect("functions array");
+    let status_of = |name: &str| {
+        functions
+            .iter()
+            .find(|entry| entry["name"] == name)
+            .map(|entry| entry["status"].as_str().unwrap().to_string())
+    };
+    assert_eq!(status_of("run").as_deref(), Some("modified"));
+    assert_eq!(status_of("legacy").as_deref(), Some("removed"));
+    assert_eq!(status_of("Lexer::next").as_deref(), Some("added"));
+    assert_eq!(
+        status_of("tokenize"),
+        None,
+        "unchanged functions are omitted"
+    );
+}
+
+#[test]
+fn diff_accepts_saved_json_reports() {
+    let saved =
+        std::env::temp_dir().join(format!("loc-checker-diff-old-{}.json", std::process::id()));
+    let output = Command::new(BIN)
+        .args(["--path", OLD_PATH, "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success());
+    fs::write(&saved, &output.stdout).unwrap();
+
+    let from_report = diff_json(saved.to_str().unwrap(), NEW_PATH);
+    let from_scan = diff_json(OLD_PATH, NEW_PATH);
+    assert_eq!(from_report["files"], from_scan["files"]);
+    assert_eq!(from_report["functions"], from_scan["functions"]);
+    fs::remove_file(saved).ok();
+}
+
+#[test]
+fn offender_only_reports_and_mismatched_count_modes_are_rejected() {
+    let saved = std::env::temp_dir().join(format!(
+        "loc-checker-diff-offenders-{}.json",
+        std::process::id()
+    ));
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            OLD_PATH,
+            "--output-format",
+            "json",
+            "--offenders-only",
+        ])
+        .args(["--offending-max-loc-per-file", "1"])
+        .args(["--offending-max-loc-per-fn", "1"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success());
+    fs::write(&saved, &output.stdout).unwrap();
+
+    let output = run_diff(&[saved.to_str().unwrap(), NEW_PATH]);
+    fs::remove_file(&saved).ok();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(stderr.contains("lists only offenders"), "got: {stderr}");
+
+    let saved = std::env::temp_dir().join(format!(
+        "loc-checker-diff-physical-{}.json",
+        std::process::id()
+    ));
+    let output = Command::new(BIN)
+        .args(["--path", OLD_PATH, "--output-format", "json"])
+        .args(["--count-mode", "physical"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success());
+    fs::write(&saved, &output.stdout).unwrap();
+
+    let output = run_diff(&[saved.to_str().unwrap(), NEW_PATH]);
+    fs::remove_file(&saved).ok();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("(count mode physical)") && stderr.contains("(count mode code+comments)"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn diff_tree_output_nests_changes_under_directories() {
+    let output = run_diff(&[OLD_PATH, NEW_PATH]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.contains("└── src/ (11 -> 18 loc, +7)"),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains("lexer.rs (added, 6 loc)"), "got: {stdout}");
+    assert!(stdout.contains("run (4 -> 8 loc, +4)"), "got: {stdout}");
+    assert!(stdout.contains("totals: 2 -> 3 files, 11 -> 18 loc (+7)"));
+}
+
+#[test]
+fn identical_sides_report_no_changes() {
+    let output = run_diff(&[OLD_PATH, OLD_PATH]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(stdout.contains("no LOC changes"), "got: {stdout}");
+}
+
+fn diff_json(old: &str, new: &str) -> Value {
+    let output = run_diff(&[old, new, "--output-format", "json"]);
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn run_diff(args: &[&str]) -> Output {
+    Command::new(BIN)
+        .arg("diff")
+        .args(args)
+        .output()
+        .expect("failed to run loc-checker")
+}
@@ -0,0 +1,10 @@
+pub mod parser;
+
+pub fn run(input: &str) -> usize {
+    let tokens = parser::tokenize(input);
+    let count = tokens.len();
+    if count == 0 {
+        return 0;
+    }
+    count
+}
@@ -0,0 +1,7 @@
+pub struct Lexer;
+
+impl Lexer {
+    pub fn next(&self, input: &str) -> Option<char> {
+        input.chars().next()
+    }
+}
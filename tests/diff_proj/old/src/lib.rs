@@ -0,0 +1,10 @@
+pub mod parser;
+
+pub fn run(input: &str) -> usize {
+    let tokens = parser::tokenize(input);
+    tokens.len()
+}
+
+pub fn legacy(value: u32) -> u32 {
+    value + 1
+}
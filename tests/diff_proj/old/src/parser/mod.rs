@@ -0,0 +1,3 @@
+pub fn tokenize(input: &str) -> Vec<&str> {
+    input.split_whitespace().collect()
+}
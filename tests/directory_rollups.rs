@@ -0,0 +1,110 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/rollup_proj";
+
+#[test]
+fn tree_directories_show_rollups() {
+    let stdout = run_tree(&[]);
+
+    assert!(
+        stdout.starts_with(
+            ". rollup_proj/ (4 files, 11 loc; largest fn: tokenize (4))\n\
+             ├── cli/ (1 files, 1 loc; largest fn: run (1))\n\
+             │   └── run.rs (1 loc; max fns loc: run (1) at tests/rollup_proj/cli/run.rs:1)\n\
+             ├── core/ (2 files, 7 loc; largest fn: tokenize (4))\n\
+             │   └── parse/ (2 files, 7 loc; largest fn: tokenize (4))\n\
+             │       ├── lexer/ (1 files, 4 loc; largest fn: tokenize (4))\n"
+        ),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn max_depth_collapses_deeper_directories() {
+    let stdout = run_tree(&["--max-depth", "2"]);
+
+    assert!(
+        stdout.contains("│   └── parse/ (2 files, 7 loc; largest fn: tokenize (4))\n└── main.rs")
+    );
+    assert!(!stdout.contains("lexer/ ("), "got: {stdout}");
+    assert!(!stdout.contains("tokens.rs ("), "got: {stdout}");
+}
+
+#[test]
+fn offender_mode_rollups_cover_every_scanned_file() {
+    let stdout = run_tree(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "3",
+        "--offending-max-loc-per-fn",
+        "100",
+    ]);
+
+    // Only tokens.rs is listed, but the directories still sum up everything below them.
+    assert!(
+        stdout.starts_with(
+            ". rollup_proj/ (4 files, 11 loc; largest fn: tokenize (4))\n\
+             └── core/ (2 files, 7 loc; largest fn: tokenize (4))\n\
+             \x20   └── parse/ (2 files, 7 loc; largest fn: tokenize (4))\n\
+             \x20       └── lexer/ (1 files, 4 loc; largest fn: tokenize (4))\n"
+        ),
+        "got: {stdout}"
+    );
+    assert!(!stdout.contains("cli/"), "got: {stdout}");
+}
+
+#[test]
+fn json_reports_every_directory() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--output-format",
+            "json",
+            "--max-depth",
+            "1",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+
+    let directories = report["directories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["path"].as_str().unwrap().to_string(),
+                entry["files"].as_u64().unwrap(),
+                entry["total_loc"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        directories,
+        vec![
+            ("cli".to_string(), 1, 1),
+            ("core".to_string(), 2, 7),
+            ("core/parse".to_string(), 2, 7),
+            ("core/parse/lexer".to_string(), 1, 4),
+        ]
+    );
+
+    let largest = &report["directories"][1]["largest_function"];
+    assert_eq!(largest["name"], "tokenize");
+    assert_eq!(largest["path"], "core/parse/lexer/tokens.rs");
+}
+
+fn run_tree(extra: &[&str]) -> String {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("stdout is not utf-8")
+}
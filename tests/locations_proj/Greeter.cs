@@ -0,0 +1,11 @@
+namespace Locations;
+
+public class Greeter
+{
+    public string Greet(string name) => "héllo wörld, " + name;
+
+    public string Farewell(string name)
+    {
+        return "bye, " + name;
+    }
+}
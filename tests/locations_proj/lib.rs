@@ -0,0 +1,17 @@
+pub const GREETING: &str = "héllo wörld";
+
+pub struct Counter {
+    value: u32,
+}
+
+impl Counter {
+    pub fn bump(&mut self) -> u32 {
+        self.value += 1;
+        self.value
+    }
+}
+
+pub fn run() {
+    let mut counter = Counter { value: 0 };
+    counter.bump();
+}
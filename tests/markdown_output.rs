@@ -0,0 +1,92 @@
+use std::process::Command;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/complexity_proj";
+
+#[test]
+fn markdown_renders_totals_and_tables() {
+    let output = run(&["--path", TEST_PATH, "--output-format", "markdown"]);
+
+    assert!(output.starts_with("## LOC report for `complexity_proj`\n"));
+    assert!(
+        output.contains("**65 LOC** in **2 files**"),
+        "got: {output}"
+    );
+    assert!(output.contains("| rust | 1 | 30 |\n| csharp | 1 | 35 |"));
+    assert!(output.contains(
+        "### Largest files (top 3)\n\n\
+         | File | Language | LOC | Code | Comments | Blank |\n\
+         | --- | --- | ---: | ---: | ---: | ---: |\n\
+         | `tests/complexity_proj/Rules.cs` | csharp | 35 | 35 | 0 | 6 |\n\
+         | `tests/complexity_proj/lib.rs` | rust | 30 | 30 | 0 | 3 |"
+    ));
+    assert!(output.contains(
+        "### Longest functions (top 3)\n\n\
+         | Function | LOC | Location |\n\
+         | --- | ---: | --- |\n\
+         | `classify` | 17 | `tests/complexity_proj/lib.rs:5` |"
+    ));
+    assert!(!output.contains("<details>"));
+}
+
+#[test]
+fn markdown_folds_offenders_with_their_limits() {
+    let output = run(&[
+        "--path",
+        TEST_PATH,
+        "--output-format",
+        "markdown",
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "30",
+        "--offending-max-loc-per-fn",
+        "16",
+        "--offending-max-nesting",
+        "2",
+    ]);
+
+    // Totals and rankings still cover the whole scan, not only offending files.
+    assert!(output.contains("**65 LOC** in **2 files**"));
+    assert!(
+        output.contains(
+            "<details>\n<summary>3 offenders over the configured limits</summary>\n\n\
+         | Item | Location | Rule | Actual | Limit |\n\
+         | --- | --- | --- | ---: | ---: |\n\
+         | file | `tests/complexity_proj/Rules.cs:1` | `file-too-long` | 35 | 30 |\n\
+         | `classify` | `tests/complexity_proj/lib.rs:5` | `function-too-long` | 17 | 16 |\n\
+         | `classify` | `tests/complexity_proj/lib.rs:5` | `function-too-deeply-nested` | 3 | 2 |\n\
+         \n</details>"
+        ),
+        "got: {output}"
+    );
+}
+
+#[test]
+fn markdown_notes_when_nothing_offends() {
+    let output = run(&[
+        "--path",
+        TEST_PATH,
+        "--output-format",
+        "markdown",
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "100",
+        "--offending-max-loc-per-fn",
+        "100",
+    ]);
+
+    assert!(output.ends_with("No offenders: every item is within the configured limits.\n"));
+}
+
+fn run(args: &[&str]) -> String {
+    let output = Command::new(BIN)
+        .args(args)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("output should be utf-8")
+}
@@ -0,0 +1,37 @@
+pub mod io;
+
+pub fn parse(input: &str) -> usize {
+    input.len()
+}
+
+pub mod lexer {
+    pub struct Token;
+
+    pub fn parse(input: &str) -> Vec<Token> {
+        input.chars().map(|_| Token).collect()
+    }
+
+    pub mod cursor {
+        pub fn advance(position: usize) -> usize {
+            position + 1
+        }
+    }
+}
+
+mod render {
+    pub trait Render {
+        fn render(&self) -> String {
+            String::new()
+        }
+    }
+
+    impl Render for super::lexer::Token {}
+
+    pub struct Page;
+
+    impl Page {
+        pub fn parse(&self) -> usize {
+            0
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub fn first() -> u32 {
+    let value = 1;
+    value + 1
+}
@@ -0,0 +1,90 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/rules_proj";
+
+#[test]
+fn most_specific_rule_decides_offenders() {
+    let json = run_json(&["--path", TEST_PATH]);
+
+    let files = json
+        .get("files")
+        .and_then(Value::as_array)
+        .expect("missing files array");
+    let paths = files
+        .iter()
+        .filter_map(|entry| entry.get("path").and_then(Value::as_str))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        ["src/core/engine.rs", "tools/util.rs"],
+        "src/** and tests/** budgets should absorb the other functions"
+    );
+
+    let engine = &files[0];
+    let offense = engine.get("offense").expect("missing offense");
+    assert_eq!(rule_of(offense, "file"), Some("core"));
+    assert_eq!(rule_of(offense, "function"), Some("core"));
+    assert_eq!(
+        offense
+            .get("file")
+            .and_then(|file| file.get("exceeded"))
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+
+    let util = files[1].get("offense").expect("missing offense");
+    assert_eq!(rule_of(util, "function"), Some("default"));
+}
+
+#[test]
+fn report_header_lists_configured_rules() {
+    let json = run_json(&["--path", TEST_PATH]);
+
+    let rules = json
+        .get("offender_limits")
+        .and_then(|limits| limits.get("rules"))
+        .and_then(Value::as_array)
+        .expect("missing rules");
+    let names = rules
+        .iter()
+        .filter_map(|rule| rule.get("name").and_then(Value::as_str))
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["integration-tests", "src/**", "core"]);
+}
+
+#[test]
+fn cli_default_thresholds_still_apply_outside_rules() {
+    let json = run_json(&["--path", TEST_PATH, "--offending-max-loc-per-fn", "50"]);
+
+    let paths = json
+        .get("files")
+        .and_then(Value::as_array)
+        .expect("missing files array")
+        .iter()
+        .filter_map(|entry| entry.get("path").and_then(Value::as_str))
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["src/core/engine.rs"]);
+}
+
+fn run_json(args: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(args)
+        .args(["--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(output.status.success(), "binary exited with failure");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    serde_json::from_str(&stdout).expect("invalid json output")
+}
+
+fn rule_of<'a>(offense: &'a Value, limit: &str) -> Option<&'a str> {
+    offense
+        .get(limit)
+        .and_then(|entry| entry.get("rule"))
+        .and_then(Value::as_str)
+}
@@ -0,0 +1,60 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+
+#[test]
+fn parallel_scan_matches_single_threaded_output() {
+    let single = run_json_with_jobs("1");
+    let parallel = run_json_with_jobs("4");
+
+    assert_eq!(single, parallel, "job count must not change the report");
+
+    let paths = single
+        .get("files")
+        .and_then(Value::as_array)
+        .expect("missing files array")
+        .iter()
+        .map(|entry| {
+            entry
+                .get("path")
+                .and_then(Value::as_str)
+                .expect("missing path")
+        })
+        .collect::<Vec<_>>();
+    let mut sorted = paths.clone();
+    sorted.sort_unstable();
+    assert_eq!(paths, sorted, "files should be reported in path order");
+}
+
+#[test]
+fn zero_jobs_is_rejected() {
+    let output = Command::new(BIN)
+        .args(["--path", "tests/test_proj", "--jobs", "0"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(!output.status.success(), "--jobs 0 should be rejected");
+}
+
+fn run_json_with_jobs(jobs: &str) -> Value {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests",
+            "--lang",
+            "rust,csharp",
+            "--output-format",
+            "json",
+            "--jobs",
+            jobs,
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(output.status.success(), "binary exited with failure");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    serde_json::from_str(&stdout).expect("invalid json output")
+}
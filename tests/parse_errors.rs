@@ -0,0 +1,143 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/broken_proj";
+
+#[test]
+fn unparseable_files_are_counted_and_reported_as_diagnostics() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(
+        output.status.success(),
+        "parse errors should not fail the scan by default"
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    let json: Value = serde_json::from_str(&stdout).expect("invalid json output");
+
+    let files = json
+        .get("files")
+        .and_then(Value::as_array)
+        .expect("missing files array");
+    assert_eq!(files.len(), 3, "broken files should still be listed");
+
+    let broken = files
+        .iter()
+        .find(|entry| entry.get("path").and_then(Value::as_str) == Some("src/broken.rs"))
+        .expect("missing src/broken.rs entry");
+    assert_eq!(
+        broken
+            .get("summary")
+            .and_then(|summary| summary.get("total_loc"))
+            .and_then(Value::as_u64),
+        Some(7),
+        "unparseable files should count raw non-blank lines"
+    );
+
+    let diagnostics = json
+        .get("diagnostics")
+        .and_then(Value::as_array)
+        .expect("missing diagnostics array");
+    let paths = diagnostics
+        .iter()
+        .map(|entry| {
+            assert_eq!(
+                entry.get("kind").and_then(Value::as_str),
+                Some("parse_error")
+            );
+            entry
+                .get("path")
+                .and_then(Value::as_str)
+                .expect("missing path")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["Broken.cs", "src/broken.rs"]);
+}
+
+#[test]
+fn tree_output_lists_files_with_errors() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(output.status.success(), "binary exited with failure");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    let section = stdout
+        .split_once("files with errors")
+        .map(|(_, rest)| rest)
+        .expect("missing files with errors section");
+    assert!(section.contains("tests/broken_proj/src/broken.rs:2: parse_error"));
+    assert!(!section.contains("src/lib.rs"));
+}
+
+#[test]
+fn csharp_syntax_errors_quote_the_offending_line() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("invalid json output");
+
+    let diagnostic = json["diagnostics"]
+        .as_array()
+        .expect("missing diagnostics array")
+        .iter()
+        .find(|entry| entry["path"] == "Broken.cs")
+        .expect("missing Broken.cs diagnostic");
+    assert_eq!(diagnostic["line"], 5);
+    assert_eq!(
+        diagnostic["message"],
+        "syntax error in C# source at line 5, column 19: unexpected syntax in `return 1 +;`"
+    );
+}
+
+#[test]
+fn csharp_syntax_error_columns_count_characters_not_bytes() {
+    let path = std::env::temp_dir().join(format!("loc-checker-unicode-{}.cs", std::process::id()));
+    std::fs::write(
+        &path,
+        "public class Sizes\n{\n    public int Run()\n    {\n        var größe = 1 +;\n        return größe;\n    }\n}\n",
+    )
+    .unwrap();
+    let output = Command::new(BIN)
+        .args(["--path", path.to_str().unwrap(), "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+    std::fs::remove_file(&path).ok();
+    assert!(output.status.success(), "{output:?}");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("invalid json output");
+
+    // `ö` and `ß` take two bytes each, which must not shift the column past the `;`.
+    assert_eq!(
+        json["diagnostics"][0]["message"],
+        "syntax error in C# source at line 5, column 24: unexpected syntax in `var größe = 1 +;`"
+    );
+}
+
+#[test]
+fn fail_on_parse_error_sets_exit_status() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--fail-on-parse-error"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert!(
+        !output.status.success(),
+        "--fail-on-parse-error should fail when a file cannot be parsed"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.contains("files with errors"),
+        "report should still be printed"
+    );
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(stderr.contains("could not be parsed"), "got: {stderr}");
+}
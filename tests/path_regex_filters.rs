@@ -67,3 +67,27 @@ fn exclude_path_regex_filters_files() {
         Some("src/lib.rs")
     );
 }
+
+#[test]
+fn skipped_files_do_not_enable_a_language() {
+    let languages = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_loc-checker"))
+            .args(["--path", "tests/vendored_proj", "--output-format", "json"])
+            .args(extra)
+            .output()
+            .expect("failed to run loc-checker binary");
+        assert!(output.status.success(), "binary exited with failure");
+
+        let json: Value = serde_json::from_slice(&output.stdout).expect("invalid json output");
+        json.get("languages").cloned().expect("missing languages")
+    };
+
+    assert_eq!(languages(&[]), serde_json::json!(["rust", "csharp"]));
+    for skip in [
+        ["--exclude", "vendor"],
+        ["--exclude-path", r"\.cs$"],
+        ["--include-path", "^src/"],
+    ] {
+        assert_eq!(languages(&skip), serde_json::json!(["rust"]), "{skip:?}");
+    }
+}
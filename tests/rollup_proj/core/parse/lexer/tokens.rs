@@ -0,0 +1,4 @@
+pub fn tokenize(input: &str) -> usize {
+    let trimmed = input.trim();
+    trimmed.len()
+}
@@ -0,0 +1,3 @@
+pub fn parse(input: &str) -> bool {
+    !input.is_empty()
+}
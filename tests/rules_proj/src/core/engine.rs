@@ -0,0 +1,6 @@
+pub fn step(value: i32) -> i32 {
+    let next = value + 1;
+    next * 2
+}
+
+pub const LIMIT: i32 = 10;
@@ -0,0 +1,5 @@
+pub fn moderate(value: i32) -> i32 {
+    let a = value + 1;
+    let b = a * 2;
+    b - 3
+}
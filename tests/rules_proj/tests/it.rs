@@ -0,0 +1,6 @@
+pub fn long_helper(value: i32) -> i32 {
+    let a = value + 1;
+    let b = a * 2;
+    let c = b - 3;
+    c / 4
+}
@@ -0,0 +1,4 @@
+pub fn helper(value: i32) -> i32 {
+    let doubled = value * 2;
+    doubled + 1
+}
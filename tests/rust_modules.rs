@@ -0,0 +1,101 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/modules_proj";
+
+#[test]
+fn functions_in_inline_modules_are_qualified() {
+    let summary = lib_summary();
+
+    assert_eq!(
+        names(&summary["file_scope_functions"]),
+        vec!["lexer::cursor::advance", "lexer::parse", "parse"]
+    );
+    assert_eq!(
+        names(&summary["structs"]),
+        vec!["lexer::Token", "render::Page"]
+    );
+    assert_eq!(summary["impl_methods"][0]["impl_target"], "render::Page");
+    assert_eq!(summary["trait_methods"][0]["trait_name"], "render::Render");
+}
+
+#[test]
+fn modules_are_listed_with_their_loc() {
+    let summary = lib_summary();
+    let modules = summary["modules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["name"].as_str().unwrap().to_string(),
+                entry["loc"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        modules,
+        vec![
+            ("render".to_string(), 14),
+            ("lexer".to_string(), 11),
+            ("lexer::cursor".to_string(), 5),
+        ]
+    );
+}
+
+#[test]
+fn tree_view_nests_modules_under_their_file() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--show-modules"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    let expected = "\
+    └── lib.rs (29 loc; max fns loc: \
+lexer::cursor::advance (3) at tests/modules_proj/src/lib.rs:15, \
+lexer::parse (3) at tests/modules_proj/src/lib.rs:10, \
+parse (3) at tests/modules_proj/src/lib.rs:3)
+        ├── mod lexer (11 loc)
+        │   └── mod cursor (5 loc)
+        └── mod render (14 loc)";
+    assert!(stdout.contains(expected), "got:\n{stdout}");
+
+    let plain = Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .output()
+        .expect("failed to run loc-checker");
+    let plain = String::from_utf8(plain.stdout).expect("stdout is not utf-8");
+    assert!(!plain.contains("mod lexer"), "modules are opt-in");
+}
+
+fn lib_summary() -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+    report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == "src/lib.rs")
+        .expect("src/lib.rs missing")["summary"]
+        .clone()
+}
+
+fn names(entries: &Value) -> Vec<String> {
+    let mut names = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
@@ -0,0 +1,113 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/rust_tests_proj";
+
+#[test]
+fn path_qualified_and_parameterized_attributes_mark_tests() {
+    let summary = scan_summary(&[]);
+
+    let tests = names(&summary["test_functions"]);
+    for expected in [
+        "async_runtime_test",
+        "parameterized_case",
+        "listed_case",
+        "benchmark",
+    ] {
+        assert!(tests.contains(&expected.to_string()), "missing {expected}");
+    }
+    assert_eq!(
+        names(&summary["file_scope_functions"]),
+        vec!["custom_attribute_test", "production"]
+    );
+}
+
+#[test]
+fn cfg_test_module_contents_are_test_code() {
+    let summary = scan_summary(&[]);
+
+    let tests = names(&summary["test_functions"]);
+    assert!(tests.contains(&"tests::build_fixture".to_string()));
+    assert!(tests.contains(&"tests::parses_input".to_string()));
+    assert!(tests.contains(&"tests::Parser::parse_twice".to_string()));
+
+    let impl_methods = summary["impl_methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["method_name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(impl_methods, vec!["parse"]);
+}
+
+#[test]
+fn cfg_all_and_any_with_test_are_test_code() {
+    let summary = scan_summary(&[]);
+
+    let tests = names(&summary["test_functions"]);
+    assert!(tests.contains(&"slow_tests::slow_fixture".to_string()));
+    assert!(tests.contains(&"test_support::fake_parser".to_string()));
+    // `not(test)` compiles outside tests, so it stays production code.
+    assert_eq!(names(&summary["structs"]), vec!["Parser", "ReleaseOnly"]);
+}
+
+#[test]
+fn configured_attribute_names_mark_tests() {
+    let summary = scan_summary(&["--test-attribute", "my_crate::integration"]);
+
+    assert!(names(&summary["test_functions"]).contains(&"custom_attribute_test".to_string()));
+    assert_eq!(names(&summary["file_scope_functions"]), vec!["production"]);
+}
+
+#[test]
+fn tests_can_be_excluded_from_offender_limits() {
+    let limits = [
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "1000",
+        "--offending-max-loc-per-fn",
+        "3",
+    ];
+
+    let with_tests = scan_report(&limits);
+    let summary = &with_tests["files"][0]["summary"];
+    assert!(!summary["test_functions"].as_array().unwrap().is_empty());
+
+    let mut args = limits.to_vec();
+    args.push("--exclude-tests-from-offenders");
+    let without_tests = scan_report(&args);
+    assert_eq!(without_tests["offender_limits"]["exclude_tests"], true);
+    let summary = &without_tests["files"][0]["summary"];
+    assert!(summary["test_functions"].as_array().unwrap().is_empty());
+    assert_eq!(
+        names(&summary["file_scope_functions"]),
+        vec!["custom_attribute_test", "production"]
+    );
+}
+
+fn scan_summary(args: &[&str]) -> Value {
+    scan_report(args)["files"][0]["summary"].clone()
+}
+
+fn scan_report(args: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .args(args)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn names(entries: &Value) -> Vec<String> {
+    let mut names = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
@@ -0,0 +1,75 @@
+pub fn production(value: u32) -> u32 {
+    let doubled = value * 2;
+    doubled + 1
+}
+
+pub struct Parser;
+
+impl Parser {
+    pub fn parse(&self, input: &str) -> usize {
+        input.len()
+    }
+}
+
+#[tokio::test]
+async fn async_runtime_test() {
+    assert_eq!(production(1), 3);
+}
+
+#[rstest]
+fn parameterized_case() {
+    assert_eq!(production(2), 5);
+}
+
+#[test_case(1)]
+fn listed_case(value: u32) {
+    assert!(production(value) > 0);
+}
+
+#[bench]
+fn benchmark(b: &mut Bencher) {
+    b.iter(|| production(3));
+}
+
+#[my_crate::integration]
+fn custom_attribute_test() {
+    assert_eq!(production(0), 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_fixture() -> Parser {
+        Parser
+    }
+
+    impl Parser {
+        fn parse_twice(&self, input: &str) -> usize {
+            self.parse(input) * 2
+        }
+    }
+
+    #[test]
+    fn parses_input() {
+        let parser = build_fixture();
+        assert_eq!(parser.parse_twice("ab"), 4);
+    }
+}
+
+#[cfg(all(test, feature = "slow"))]
+mod slow_tests {
+    fn slow_fixture() -> u32 {
+        1
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+pub mod test_support {
+    pub fn fake_parser() -> super::Parser {
+        super::Parser
+    }
+}
+
+#[cfg(not(test))]
+pub struct ReleaseOnly;
@@ -0,0 +1,175 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/complexity_proj";
+
+#[test]
+fn sarif_log_lists_rules_and_tool() {
+    let log = run_sarif(
+        TEST_PATH,
+        &[
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+        ],
+    );
+
+    assert_eq!(log["version"], "2.1.0");
+    let driver = &log["runs"][0]["tool"]["driver"];
+    assert_eq!(driver["name"], "loc-checker");
+    let rule_ids = driver["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert!(rule_ids.contains(&"file-too-long"));
+    assert!(rule_ids.contains(&"function-too-long"));
+}
+
+#[test]
+fn each_exceeded_limit_becomes_a_result() {
+    let log = run_sarif(
+        TEST_PATH,
+        &[
+            "--offending-max-loc-per-file",
+            "30",
+            "--offending-max-loc-per-fn",
+            "16",
+            "--offending-max-nesting",
+            "2",
+        ],
+    );
+
+    let results = results(&log);
+    let summary = results
+        .iter()
+        .map(|result| {
+            (
+                result["ruleId"].as_str().unwrap(),
+                result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+                    .as_str()
+                    .unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("file-too-long", "tests/complexity_proj/Rules.cs"),
+            ("function-too-long", "tests/complexity_proj/lib.rs"),
+            ("function-too-deeply-nested", "tests/complexity_proj/lib.rs"),
+        ]
+    );
+
+    let too_long = &results[1];
+    assert_eq!(
+        too_long["message"]["text"],
+        "Function `classify` has 17 LOC, above the limit of 16 (rule `default`)"
+    );
+    assert_eq!(too_long["properties"]["actual"], 17);
+    assert_eq!(too_long["properties"]["threshold"], 16);
+    let region = &too_long["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 5);
+    assert_eq!(region["endLine"], 21);
+    assert_eq!(
+        too_long["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+        "classify"
+    );
+    assert_eq!(
+        results[2]["message"]["text"],
+        "Function `classify` has nesting depth 3, above the limit of 2"
+    );
+}
+
+#[test]
+fn partial_types_point_at_every_declaration() {
+    let log = run_sarif(
+        "tests/csharp_partial_proj",
+        &[
+            "--offending-max-loc-per-file",
+            "100",
+            "--offending-max-loc-per-fn",
+            "100",
+            "--offending-max-loc-per-type",
+            "20",
+        ],
+    );
+
+    let results = results(&log);
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["ruleId"], "type-too-long");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "tests/csharp_partial_proj/Customer.Api.cs"
+    );
+    assert_eq!(result["relatedLocations"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn sarif_requires_offender_thresholds() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "sarif"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("--offending-max-loc-per-file"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn diff_rejects_sarif() {
+    let output = Command::new(BIN)
+        .args(["diff", TEST_PATH, TEST_PATH, "--output-format", "sarif"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("supports only the tree and json output formats"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn artifact_uris_are_percent_encoded() {
+    let log = run_sarif(
+        "tests/odd paths",
+        &[
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "2",
+        ],
+    );
+
+    let results = results(&log);
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "tests/odd%20paths/lib%20%231.rs"
+    );
+}
+
+fn run_sarif(path: &str, extra: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", path, "--output-format", "sarif"])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn results(log: &Value) -> Vec<Value> {
+    log["runs"][0]["results"].as_array().unwrap().clone()
+}
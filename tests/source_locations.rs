@@ -0,0 +1,122 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/locations_proj";
+
+#[test]
+fn rust_entries_carry_lines_and_columns() {
+    let report = run_json(&[]);
+    let summary = file_summary(&report, "lib.rs");
+
+    assert_eq!(
+        location(&summary["impl_methods"][0]),
+        (8, 5, 11, 5),
+        "bump starts and ends on indented lines"
+    );
+    assert_eq!(location(&summary["impl_blocks"][0]), (7, 1, 12, 1));
+    assert_eq!(summary["impl_blocks"][0]["kind"], "impl");
+    assert_eq!(location(&summary["structs"][0]), (3, 1, 5, 1));
+    // Columns count characters, so the accented string literal ends at column 41.
+    assert_eq!(location(&summary["consts"][0]), (1, 1, 1, 41));
+}
+
+#[test]
+fn csharp_entries_carry_lines_and_columns() {
+    let report = run_json(&[]);
+    let summary = file_summary(&report, "Greeter.cs");
+
+    let methods = summary["impl_methods"].as_array().unwrap();
+    let greet = methods
+        .iter()
+        .find(|entry| entry["method_name"] == "Greet")
+        .unwrap();
+    // The line is 65 bytes long but holds 63 characters.
+    assert_eq!(location(greet), (5, 5, 5, 63));
+    assert_eq!(summary["impl_blocks"][0]["kind"], "type_body");
+    assert_eq!(summary["impl_blocks"][0]["trait_name"], Value::Null);
+    // A file-scoped namespace runs to the last line of the file, not past its final newline.
+    assert_eq!(location(&summary["namespaces"][0]), (1, 1, 11, 1));
+}
+
+#[test]
+fn tree_links_offending_functions_to_their_lines() {
+    let output = run_tree(&[
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "1000",
+        "--offending-max-loc-per-fn",
+        "2",
+    ]);
+
+    assert!(
+        output.contains(
+            "├── Greeter.cs (9 loc)\n\
+             │   └── Locations.Greeter::Farewell (4 loc) at tests/locations_proj/Greeter.cs:7\n"
+        ),
+        "got: {output}"
+    );
+    assert!(
+        output.contains(
+            "    ├── Counter::bump (4 loc) at tests/locations_proj/lib.rs:8\n\
+             \x20   └── run (4 loc) at tests/locations_proj/lib.rs:14\n"
+        ),
+        "got: {output}"
+    );
+}
+
+#[test]
+fn longest_functions_link_to_their_lines() {
+    let output = run_tree(&[]);
+
+    assert!(
+        output.ends_with(
+            "longest functions (top 3)\n\
+             ├── Locations.Greeter.Farewell (4 loc) at tests/locations_proj/Greeter.cs:7\n\
+             ├── Counter::bump (4 loc) at tests/locations_proj/lib.rs:8\n\
+             └── run (4 loc) at tests/locations_proj/lib.rs:14\n"
+        ),
+        "got: {output}"
+    );
+}
+
+fn run_json(extra: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn run_tree(extra: &[&str]) -> String {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("stdout is not utf-8")
+}
+
+fn file_summary(report: &Value, path: &str) -> Value {
+    report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == path)
+        .unwrap_or_else(|| panic!("{path} missing from report"))["summary"]
+        .clone()
+}
+
+fn location(entry: &Value) -> (u64, u64, u64, u64) {
+    let location = &entry["location"];
+    (
+        location["start_line"].as_u64().unwrap(),
+        location["start_column"].as_u64().unwrap(),
+        location["end_line"].as_u64().unwrap(),
+        location["end_column"].as_u64().unwrap(),
+    )
+}
@@ -0,0 +1,26 @@
+namespace Bank;
+
+public class Account
+{
+    private int balance;
+
+    public int Balance
+    {
+        get { return balance; }
+        set { balance = value; }
+    }
+}
+
+[TestClass]
+public class AccountTests
+{
+    [TestMethod]
+    public void Deposits()
+    {
+    }
+
+    private int Seed
+    {
+        get { return 1; }
+    }
+}
@@ -0,0 +1,140 @@
+use std::process::Command;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+
+#[test]
+fn csv_lists_every_item_with_a_header_row() {
+    let output = run(&["--path", "tests/table_proj", "--output-format", "csv"]);
+
+    // Paths inside generic arguments stay in one piece: impl targets are never split, and
+    // qualified names only split at the last `::` outside angle brackets.
+    let expected = [
+        "path,language,kind,container,name,loc",
+        "lib.rs,rust,fn,convert,into_wrap,3",
+        "lib.rs,rust,test,,pair_has_key,5",
+        "lib.rs,rust,impl method,\"Pair < K , V >\",key,3",
+        "lib.rs,rust,impl method,\"fmt :: Display for Pair < String , String >\",fmt,3",
+        "lib.rs,rust,impl method,Wrap < std :: io :: Error >,kind,3",
+        "lib.rs,rust,struct,,Pair,4",
+        "lib.rs,rust,struct,,Wrap,1",
+        "lib.rs,rust,trait,,Shape,3",
+        "lib.rs,rust,impl block,,\"impl Pair < K , V >\",5",
+        "lib.rs,rust,impl block,,\"impl fmt :: Display for Pair < String , String >\",5",
+        "lib.rs,rust,impl block,,impl Wrap < std :: io :: Error >,5",
+        "lib.rs,rust,static,,LIMIT,1",
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn tsv_splits_qualified_names_into_container_and_name() {
+    let output = run(&["--path", "tests/locations_proj", "--output-format", "tsv"]);
+    let rows = output
+        .lines()
+        .map(|line| line.split('\t').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        rows[0],
+        ["path", "language", "kind", "container", "name", "loc"]
+    );
+    assert!(rows.iter().all(|row| row.len() == 6));
+    assert!(rows.contains(&vec![
+        "Greeter.cs",
+        "csharp",
+        "impl method",
+        "Locations.Greeter",
+        "Farewell",
+        "4",
+    ]));
+    assert!(rows.contains(&vec![
+        "Greeter.cs",
+        "csharp",
+        "struct",
+        "Locations",
+        "Greeter",
+        "8"
+    ]));
+    assert!(rows.contains(&vec![
+        "lib.rs",
+        "rust",
+        "impl method",
+        "Counter",
+        "bump",
+        "4"
+    ]));
+    assert!(rows.contains(&vec!["lib.rs", "rust", "const", "", "GREETING", "1"]));
+}
+
+#[test]
+fn csharp_accessors_split_the_same_way_for_every_kind() {
+    let output = run(&[
+        "--path",
+        "tests/table_csharp_proj",
+        "--output-format",
+        "csv",
+    ]);
+    let lines = output.lines().collect::<Vec<_>>();
+
+    // Test and impl-method accessors both keep their property in the name column.
+    assert!(lines.contains(&"Account.cs,csharp,test,Bank.AccountTests,Seed.get,1"));
+    assert!(lines.contains(&"Account.cs,csharp,impl method,Bank.Account,Balance.get,1"));
+    assert!(lines.contains(&"Account.cs,csharp,test,Bank.AccountTests,Deposits,4"));
+}
+
+#[test]
+fn csv_keeps_only_offending_files_in_offender_mode() {
+    let output = run(&[
+        "--path",
+        "tests/test_proj",
+        "--output-format",
+        "csv",
+        "--offenders-only",
+        "--offending-max-loc-per-file",
+        "100",
+        "--offending-max-loc-per-fn",
+        "70",
+    ]);
+
+    let paths = output
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').next().unwrap())
+        .collect::<Vec<_>>();
+    assert!(!paths.is_empty());
+    assert!(
+        paths.iter().all(|path| *path == "src/main.rs"),
+        "got: {output}"
+    );
+}
+
+#[test]
+fn diff_rejects_table_formats() {
+    let output = Command::new(BIN)
+        .args([
+            "diff",
+            "tests/test_proj",
+            "tests/test_proj",
+            "--output-format",
+            "csv",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("supports only the tree and json output formats"));
+}
+
+fn run(args: &[&str]) -> String {
+    let output = Command::new(BIN)
+        .args(args)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("output should be utf-8")
+}
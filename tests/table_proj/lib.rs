@@ -0,0 +1,44 @@
+use std::fmt;
+
+pub static LIMIT: usize = 10;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Pair<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K, V> Pair<K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl fmt::Display for Pair<String, String> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t{}", self.key, self.value)
+    }
+}
+
+#[test]
+fn pair_has_key() {
+    let pair = Pair { key: 1, value: 2 };
+    assert_eq!(*pair.key(), 1);
+}
+
+pub struct Wrap<E>(E);
+
+impl Wrap<std::io::Error> {
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.0.kind()
+    }
+}
+
+pub mod convert {
+    pub fn into_wrap<T>(value: T) -> super::Wrap<T> {
+        super::Wrap(value)
+    }
+}
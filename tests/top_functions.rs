@@ -0,0 +1,100 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/test_proj";
+
+#[test]
+fn top_limits_functions_per_file_and_across_the_scan() {
+    let report = run_json(&["--top", "1"]);
+
+    assert_eq!(report["top"], 1);
+    for file in report["files"].as_array().unwrap() {
+        assert_eq!(
+            file["summary"]["top_functions"].as_array().unwrap().len(),
+            1
+        );
+    }
+    assert_eq!(
+        ranking(&report),
+        vec![(
+            "src/main.rs".to_string(),
+            "build_full_report".to_string(),
+            79
+        )]
+    );
+}
+
+#[test]
+fn top_all_keeps_every_function() {
+    let report = run_json(&["--top", "all"]);
+
+    assert_eq!(report["top"], "all");
+    let main = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"] == "src/main.rs")
+        .expect("missing main.rs");
+    assert_eq!(
+        main["summary"]["top_functions"].as_array().unwrap().len(),
+        5
+    );
+    assert_eq!(ranking(&report).len(), 9);
+}
+
+#[test]
+fn default_ranking_lists_three_longest_functions() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.ends_with(
+            "longest functions (top 3)\n\
+             ├── build_full_report (79 loc) at tests/test_proj/src/main.rs:15\n\
+             ├── compute_series_a (65 loc) at tests/test_proj/src/lib.rs:4\n\
+             └── compute_series_b (64 loc) at tests/test_proj/src/lib.rs:70\n"
+        ),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn top_rejects_zero() {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--top", "0"])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+fn run_json(extra: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args(["--path", TEST_PATH, "--output-format", "json"])
+        .args(extra)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+fn ranking(report: &Value) -> Vec<(String, String, u64)> {
+    report["longest_functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["path"].as_str().unwrap().to_string(),
+                entry["name"].as_str().unwrap().to_string(),
+                entry["loc"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,157 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+const BIN: &str = env!("CARGO_BIN_EXE_loc-checker");
+const TEST_PATH: &str = "tests/types_proj";
+
+#[test]
+fn type_limit_keeps_only_offending_definitions() {
+    let summary = lib_summary(&["--offending-max-loc-per-type", "5"]);
+
+    assert_eq!(names(&summary["structs"]), vec!["Settings"]);
+    assert!(summary["enums"].as_array().unwrap().is_empty());
+    assert!(
+        summary["impl_blocks"].as_array().unwrap().is_empty(),
+        "impl blocks are unchecked without --offending-max-loc-per-impl-block"
+    );
+}
+
+#[test]
+fn impl_block_limit_flags_large_impl_blocks() {
+    let summary = lib_summary(&["--offending-max-loc-per-impl-block", "10"]);
+
+    let targets = summary["impl_blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|block| block["impl_target"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(targets, vec!["Settings"]);
+    assert!(summary["structs"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn check_counts_type_offenders() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--check",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+            "--offending-max-loc-per-type",
+            "5",
+            "--offending-max-loc-per-impl-block",
+            "10",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("0 file(s), 0 function(s) and 2 type(s) exceed configured limits"),
+        "got: {stderr}"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.contains("types over limit: Settings (7), impl Settings (11)"),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn csharp_classes_are_reported_once_as_types() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests/locations_proj/Greeter.cs",
+            "--check",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+            "--offending-max-loc-per-type",
+            "5",
+            "--offending-max-loc-per-impl-block",
+            "5",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is not utf-8");
+    assert!(
+        stderr.contains("0 file(s), 0 function(s) and 1 type(s) exceed configured limits"),
+        "got: {stderr}"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+    assert!(
+        stdout.contains("types over limit: Locations.Greeter (8))"),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn partial_declarations_are_judged_by_merged_size() {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            "tests/csharp_partial_proj",
+            "--output-format",
+            "json",
+            "--offenders-only",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+            "--offending-max-loc-per-type",
+            "10",
+            "--offending-max-loc-per-impl-block",
+            "5",
+        ])
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+
+    // Customer.Validation.cs declares 11 lines of the partial class, but only the merged type
+    // is reported, neither as a type definition nor as an impl block.
+    assert!(report["files"].as_array().unwrap().is_empty());
+    assert_eq!(report["partial_types"][0]["name"], "Shop.Domain.Customer");
+}
+
+fn lib_summary(limits: &[&str]) -> Value {
+    let output = Command::new(BIN)
+        .args([
+            "--path",
+            TEST_PATH,
+            "--output-format",
+            "json",
+            "--offenders-only",
+            "--offending-max-loc-per-file",
+            "1000",
+            "--offending-max-loc-per-fn",
+            "1000",
+        ])
+        .args(limits)
+        .output()
+        .expect("failed to run loc-checker");
+    assert!(output.status.success(), "{output:?}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("stdout is not json");
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "only lib.rs holds offending types");
+    files[0]["summary"].clone()
+}
+
+fn names(entries: &Value) -> Vec<String> {
+    entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect()
+}
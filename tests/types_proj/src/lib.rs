@@ -0,0 +1,34 @@
+pub struct Settings {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub verbose: bool,
+}
+
+pub struct Flag(bool);
+
+pub enum Mode {
+    Fast,
+    Slow,
+}
+
+impl Settings {
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    pub fn volume(&self) -> u32 {
+        self.area() * self.depth
+    }
+
+    pub fn label(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Flag {
+    pub fn get(&self) -> bool {
+        self.0
+    }
+}